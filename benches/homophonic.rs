@@ -0,0 +1,50 @@
+use std::env;
+use std::time::{Duration, Instant};
+use szyfry::algorithms::{Cipher, HomophonicData};
+use szyfry::utils::{Key, KeyRule};
+use szyfry::Alphabet;
+
+/// Rozmiar danych testowych w megabajtach (zmienna środowiskowa `SZYFRY_BENCH_MB`)
+fn size() -> usize {
+    env::var("SZYFRY_BENCH_MB").ok()
+                               .and_then(|value: String| value.parse().ok())
+                               .unwrap_or(16)
+}
+
+/// Odszyfrowywanie przez przeszukiwanie wszystkich pozycji klucza dla każdego znaku
+fn linear_decrypt(code: &Key, alphabet: &Alphabet, data: &str) -> String {
+    data.chars()
+        .map(|sign: char| {
+            match code.data.iter().take(alphabet.len()).position(|v: &String| v.contains(sign)) {
+                Some(index) => alphabet.symbol(index),
+                None => sign
+            }
+        })
+        .collect::<String>()
+}
+
+/// Wyświetla czas wykonania i przepustowość
+fn report(name: &str, bytes: usize, elapsed: Duration) {
+    let throughput: f64 = bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64();
+    println!("{:<24} {:>10.3?} {:>10.2} MB/s", name, elapsed, throughput);
+}
+
+fn main() {
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let algorithm: HomophonicData = HomophonicData::new(code.clone()).unwrap();
+
+    let plain: String = "Ala ma kota a kot ma Ale ".repeat(size() * 1024 * 1024 / 25);
+    let encrypted: String = algorithm.encrypt(&plain);
+    println!("homophonic decryption of {} MB", size());
+
+    let start: Instant = Instant::now();
+    let linear: String = linear_decrypt(&code, &Alphabet::latin_mixed(), &encrypted);
+    report("linear scan", encrypted.len(), start.elapsed());
+
+    let start: Instant = Instant::now();
+    let indexed: String = algorithm.decrypt(&encrypted);
+    report("reverse index", encrypted.len(), start.elapsed());
+
+    assert_eq!(linear, indexed);
+    assert_eq!(indexed, plain);
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::env;
+use std::time::{Duration, Instant};
+use szyfry::algorithms::{Cipher, PolyalphabeticData};
+use szyfry::utils::{Key, KeyRule};
+
+/// Rozmiar danych testowych w megabajtach (zmienna środowiskowa `SZYFRY_BENCH_MB`)
+fn size() -> usize {
+    env::var("SZYFRY_BENCH_MB").ok()
+                               .and_then(|value: String| value.parse().ok())
+                               .unwrap_or(100)
+}
+
+/// Szyfrowanie z mapą budowaną przy każdym wywołaniu i wyszukiwaniem liniowym wyniku
+fn hashmap_encrypt(code: &Key, data: &str) -> String {
+    let ascii: HashMap<char, usize> = ('A'..='Z').chain('a'..='z')
+                                                .enumerate()
+                                                .map(|(index, sign): (usize, char)| (sign, index))
+                                                .collect();
+
+    data.chars().zip(code.data.iter().cycle()).map(|(sign, vec): (char, &String)| {
+        if sign.is_ascii_alphabetic() {
+            let code_char: char = vec.chars().next().unwrap();
+            let result: usize = (ascii[&sign] + ascii[&code_char]) % ascii.len();
+            *ascii.iter().find(|(_k, &v): &(&char, &usize)| v == result).unwrap().0
+        } else {
+            sign
+        }
+    }).collect::<String>()
+}
+
+/// Wyświetla czas wykonania i przepustowość
+fn report(name: &str, bytes: usize, elapsed: Duration) {
+    let throughput: f64 = bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64();
+    println!("{:<24} {:>10.3?} {:>10.2} MB/s", name, elapsed, throughput);
+}
+
+fn main() {
+    let code: Key = Key::read_translation("ascii_only.ckey", KeyRule::Column).unwrap();
+    let algorithm: PolyalphabeticData = PolyalphabeticData::new(code.clone()).unwrap();
+
+    let plain: String = "Ala ma kota a kot ma Ale ".repeat(size() * 1024 * 1024 / 25);
+    println!("polyalphabetic encryption of {} MB", size());
+
+    let start: Instant = Instant::now();
+    let before: String = hashmap_encrypt(&code, &plain);
+    report("hashmap (before)", plain.len(), start.elapsed());
+
+    let start: Instant = Instant::now();
+    let after: String = algorithm.encrypt(&plain);
+    report("tables (after)", plain.len(), start.elapsed());
+
+    let start: Instant = Instant::now();
+    let decrypted: String = algorithm.decrypt(&after);
+    report("tables decryption", after.len(), start.elapsed());
+
+    assert_eq!(before, after);
+    assert_eq!(decrypted, plain);
+}
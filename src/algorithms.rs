@@ -1,10 +1,20 @@
+pub mod affine;
+pub mod bytes;
 pub mod ceasar;
 pub mod homophonics;
+pub mod nomenclator;
+pub mod numeric;
 pub mod polyalphabetic;
 
+pub use affine::AffineData;
+pub use bytes::{ByteCeasarData, BytePolyalphabeticData};
 pub use ceasar::CeasarData;
-pub use homophonics::HomophonicData;
-pub use polyalphabetic::PolyalphabeticData;
+pub use homophonics::{HomophoneStrategy, HomophonicData, HomophonicKeyParams, NullPlacement};
+pub use nomenclator::{Codebook, NomenclatorData};
+pub use numeric::{NumericFormat, NumericHomophonicData, NumericKey, NumericKeyParams};
+pub use polyalphabetic::{KeyAdvance, PolyalphabeticData, PolyalphabeticKeyParams};
+
+use crate::Error;
 
 /// Wspólny interfejs szyfrów
 pub trait Cipher {
@@ -21,4 +31,170 @@ pub trait Cipher {
     /// 
     /// * `data` - ciąg znaków do odszyfrowania
     fn decrypt(&self, data: &str) -> String;
+
+    /// Metoda do szyfrowania podanego ciągu znaków, która zamiast przepisywać
+    /// znaki, których szyfr nie obsługuje, może zgłosić błąd
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - ciąg znaków do zaszyfrowania
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - tekst zawiera znak, którego nie można zaszyfrować
+    fn try_encrypt(&self, data: &str) -> Result<String, Error> {
+        Ok(self.encrypt(data))
+    }
+
+    /// Metoda do odszyfrowywania podanego ciągu znaków, która zamiast pomijać
+    /// nieznane znaki zgłasza błąd (zgodnie z ustawionym [`Unknown`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - ciąg znaków do odszyfrowania
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - szyfrogram zawiera znak spoza klucza
+    fn try_decrypt(&self, data: &str) -> Result<String, Error> {
+        Ok(self.decrypt(data))
+    }
+
+    /// Metoda do szyfrowania fragmentu dłuższego tekstu, kontynuująca od podanej pozycji
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - fragment do zaszyfrowania
+    /// * `position` - pozycja po poprzednich fragmentach (aktualizowana po zaszyfrowaniu)
+    fn encrypt_at(&self, data: &str, position: &mut Position) -> String {
+        position.characters += data.chars().count();
+        self.encrypt(data)
+    }
+
+    /// Metoda do szyfrowania fragmentu dłuższego tekstu, kontynuująca od podanej pozycji
+    /// (działa jak [`Cipher::try_encrypt`], pozycje w błędach liczone są od początku tekstu)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - fragment do zaszyfrowania
+    /// * `position` - pozycja po poprzednich fragmentach (aktualizowana po zaszyfrowaniu)
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - tekst zawiera znak, którego nie można zaszyfrować
+    fn try_encrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        Ok(self.encrypt_at(data, position))
+    }
+
+    /// Metoda do odszyfrowywania fragmentu dłuższego tekstu, kontynuująca od podanej pozycji
+    /// (działa jak [`Cipher::try_decrypt`], pozycje w błędach liczone są od początku tekstu)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - fragment do odszyfrowania
+    /// * `position` - pozycja po poprzednich fragmentach (aktualizowana po odszyfrowaniu)
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - szyfrogram zawiera znak spoza klucza
+    fn try_decrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        let output: String = self.try_decrypt(data).map_err(|error: Error| error.shifted(position.characters))?;
+        position.characters += data.chars().count();
+        Ok(output)
+    }
+}
+
+/// Wspólny interfejs szyfrów działających na bajtach (dowolne pliki binarne)
+pub trait ByteCipher {
+    /// Metoda do szyfrowania pojedynczego bajtu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `byte` - bajt do zaszyfrowania
+    /// * `position` - pozycja bajtu w danych
+    fn encrypt_byte(&self, byte: u8, position: usize) -> u8;
+
+    /// Metoda do odszyfrowywania pojedynczego bajtu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `byte` - bajt do odszyfrowania
+    /// * `position` - pozycja bajtu w danych
+    fn decrypt_byte(&self, byte: u8, position: usize) -> u8;
+
+    /// Metoda do szyfrowania podanych bajtów
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - bajty do zaszyfrowania
+    fn encrypt_bytes(&self, data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .enumerate()
+            .map(|(position, &byte): (usize, &u8)| self.encrypt_byte(byte, position))
+            .collect()
+    }
+
+    /// Metoda do odszyfrowywania podanych bajtów
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - bajty do odszyfrowania
+    fn decrypt_bytes(&self, data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .enumerate()
+            .map(|(position, &byte): (usize, &u8)| self.decrypt_byte(byte, position))
+            .collect()
+    }
+}
+
+/// Pozycja w dłuższym tekście przetwarzanym fragmentami
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    /// Ilość przetworzonych znaków
+    pub characters: usize,
+    /// Ilość wykorzystanych pozycji klucza (dla szyfrów z kluczem okresowym)
+    pub key: usize,
+    /// Czy bieżąca linijka szyfrogramu zawiera już elementy (dla szyfrów rozdzielających elementy spacjami)
+    pub line_started: bool
+}
+
+/// Do określania co zrobić ze znakiem, którego nie ma w kluczu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unknown {
+    /// Zgłoś błąd (w przypadku metod nie zwracających błędów znak jest przepisywany)
+    #[default]
+    Reject,
+    /// Przepisz znak bez zmian
+    Keep,
+    /// Zastąp znak podanym symbolem
+    Replace(char)
+}
+
+/// Metody obsługi nieznanych znaków
+impl Unknown {
+    /// Zwraca znak, który należy wstawić w miejsce nieznanego znaku
+    /// 
+    /// # Arguments
+    /// 
+    /// * `character` - nieznany znak
+    /// * `position` - pozycja znaku w tekście
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - w przypadku [`Unknown::Reject`]
+    pub fn handle(&self, character: char, position: usize) -> Result<char, Error> {
+        match self {
+            Unknown::Reject => Err(Error::UnknownSymbol { character, position }),
+            Unknown::Keep => Ok(character),
+            Unknown::Replace(placeholder) => Ok(*placeholder)
+        }
+    }
+
+    /// Zwraca wariant, który nigdy nie zgłasza błędu ([`Unknown::Reject`] zamieniany jest na [`Unknown::Keep`])
+    pub fn lenient(&self) -> Unknown {
+        match self {
+            Unknown::Reject => Unknown::Keep,
+            other => *other
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,102 @@
+use crate::algorithms::Cipher;
+use crate::{Alphabet, Error};
+
+/// Przechowuje dodatkowe infromacje potrzebne dla szyfru afinicznego (`E(x) = (a·x + b) mod m`)
+pub struct AffineData {
+    /// Mnożnik (zredukowany modulo długość alfabetu, względnie pierwszy z nią)
+    a: usize,
+    /// Przesunięcie znaków (zredukowane modulo długość alfabetu)
+    b: usize,
+    /// Odwrotność mnożnika modulo długość alfabetu (wykorzystywana przy odszyfrowywaniu)
+    a_inverse: usize,
+    /// Alfabet, w którym przekształcane są znaki
+    alphabet: Alphabet
+}
+
+/// Metody dodatkowe związane z szyfrem afinicznym
+impl AffineData {
+    /// Tworzy nową strukturę z informacjami szyfru afinicznego (alfabet łaciński z zachowaniem wielkości liter)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `a` - mnożnik (musi być względnie pierwszy z długością alfabetu)
+    /// * `b` - przesunięcie znaków (ujemne oznacza przesunięcie w lewo)
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NotCoprime`] - mnożnik nie jest względnie pierwszy z długością alfabetu
+    pub fn new(a: impl Into<i64>, b: impl Into<i64>) -> Result<AffineData, Error> {
+        AffineData::with_alphabet(a, b, Alphabet::latin_upper())
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru afinicznego dla podanego alfabetu
+    /// (`a = 1` odpowiada szyfrowi cezara, `a = b = -1` szyfrowi Atbasz)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `a` - mnożnik (musi być względnie pierwszy z długością alfabetu)
+    /// * `b` - przesunięcie znaków (ujemne oznacza przesunięcie w lewo)
+    /// * `alphabet` - alfabet, w którym przekształcane są znaki
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NotCoprime`] - mnożnik nie jest względnie pierwszy z długością alfabetu
+    pub fn with_alphabet(a: impl Into<i64>, b: impl Into<i64>, alphabet: Alphabet) -> Result<AffineData, Error> {
+        let modulus: i64 = alphabet.len() as i64;
+        let (a, b): (i64, i64) = (a.into(), b.into());
+        let a_inverse: usize = inverse(a.rem_euclid(modulus), modulus)
+            .ok_or(Error::NotCoprime { multiplier: a, modulus: alphabet.len() })? as usize;
+        Ok(AffineData { a: a.rem_euclid(modulus) as usize, b: b.rem_euclid(modulus) as usize, a_inverse, alphabet })
+    }
+
+    /// Tworzy szyfr Atbasz (odwrócenie kolejności alfabetu, `a = b = -1`) dla podanego alfabetu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `alphabet` - alfabet, w którym przekształcane są znaki
+    pub fn atbash(alphabet: Alphabet) -> AffineData {
+        AffineData::with_alphabet(-1, -1, alphabet).expect("-1 is coprime with every modulus")
+    }
+
+    /// Mnożnik i przesunięcie (z zakresu od 0 do długości alfabetu)
+    pub fn coefficients(&self) -> (usize, usize) {
+        (self.a, self.b)
+    }
+
+    /// Przekształca pojedynczy znak funkcją indeksu w alfabecie szyfru
+    /// (znaki spoza alfabetu pozostają bez zmian)
+    fn transform(&self, character: char, map: impl FnOnce(usize) -> usize) -> char {
+        self.alphabet.translate(character, map)
+                     .unwrap_or(character)
+    }
+}
+
+/// Implementacja metod szyfrujących
+impl Cipher for AffineData {
+    fn encrypt(&self, data: &str) -> String {
+        data.chars()
+            .map(|sign: char| self.transform(sign, |index: usize| self.a * index + self.b))
+            .collect::<String>()
+    }
+    fn decrypt(&self, data: &str) -> String {
+        let modulus: usize = self.alphabet.len();
+        data.chars()
+            .map(|sign: char| self.transform(sign, |index: usize| self.a_inverse * (index + modulus - self.b)))
+            .collect::<String>()
+    }
+}
+
+/// Odwrotność liczby modulo `modulus` (rozszerzony algorytm Euklidesa; `None`, gdy liczby nie są względnie pierwsze)
+fn inverse(value: i64, modulus: i64) -> Option<i64> {
+    let (mut r, mut new_r): (i64, i64) = (modulus, value);
+    let (mut t, mut new_t): (i64, i64) = (0, 1);
+    while new_r != 0 {
+        let quotient: i64 = r / new_r;
+        (r, new_r) = (new_r, r - quotient * new_r);
+        (t, new_t) = (new_t, t - quotient * new_t);
+    }
+    match r {
+        1 => Some(t.rem_euclid(modulus)),
+        _ => None
+    }
+}
@@ -0,0 +1,78 @@
+use crate::algorithms::ByteCipher;
+use crate::utils::Key;
+use crate::Error;
+
+/// Przechowuje dodatkowe infromacje potrzebne dla szyfru cezara działającego na bajtach (0-255)
+pub struct ByteCeasarData(u8);
+
+/// Metody dodatkowe związane z szyfrem cezara działającym na bajtach
+impl ByteCeasarData {
+    /// Tworzy nową strukturę z informacjami szyfru cezara działającego na bajtach
+    /// 
+    /// # Arguments
+    /// 
+    /// * `offset` - przesunięcie bajtów (brane modulo 256, ujemne oznacza przesunięcie w lewo)
+    pub fn new(offset: impl Into<i64>) -> ByteCeasarData {
+        ByteCeasarData(offset.into().rem_euclid(256) as u8)
+    }
+}
+
+/// Implementacja metod szyfrujących
+impl ByteCipher for ByteCeasarData {
+    fn encrypt_byte(&self, byte: u8, _position: usize) -> u8 {
+        byte.wrapping_add(self.0)
+    }
+    fn decrypt_byte(&self, byte: u8, _position: usize) -> u8 {
+        byte.wrapping_sub(self.0)
+    }
+}
+
+/// Przechowuje dodatkowe infromacje potrzebne dla szyfru wieloalfabetowego (Vigenère'a) działającego na bajtach
+pub struct BytePolyalphabeticData(Vec<u8>);
+
+/// Metody dodatkowe związane z szyfrem wieloalfabetowym działającym na bajtach
+impl BytePolyalphabeticData {
+    /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego działającego na bajtach
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - kolejne przesunięcia bajtów
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyTooShort`] - klucz musi posiadać co najmniej jeden bajt
+    pub fn new(code: Vec<u8>) -> Result<BytePolyalphabeticData, Error> {
+        if code.is_empty() {
+            return Err(Error::KeyTooShort { expected: 1, found: 0 })
+        }
+        Ok(BytePolyalphabeticData(code))
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego działającego na bajtach
+    /// na podstawie klucza (bajty pierwszego znaku każdej pozycji klucza w kodowaniu UTF-8)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyTooShort`] - klucz musi posiadać co najmniej jedną niepustą pozycję
+    pub fn from_key(code: &Key) -> Result<BytePolyalphabeticData, Error> {
+        let mut bytes: Vec<u8> = Vec::new();
+        for c in code.data.iter().filter_map(|vec: &String| vec.chars().next()) {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        BytePolyalphabeticData::new(bytes)
+    }
+}
+
+/// Implementacja metod szyfrujących
+impl ByteCipher for BytePolyalphabeticData {
+    fn encrypt_byte(&self, byte: u8, position: usize) -> u8 {
+        byte.wrapping_add(self.0[position % self.0.len()])
+    }
+    fn decrypt_byte(&self, byte: u8, position: usize) -> u8 {
+        byte.wrapping_sub(self.0[position % self.0.len()])
+    }
+}
@@ -1,17 +1,70 @@
 use crate::algorithms::Cipher;
+use crate::{Alphabet, Error};
 
 /// Przechowuje dodatkowe infromacje potrzebne dla szyfru cezara
-pub struct CeasarData(u8);
+pub struct CeasarData {
+    /// Przesunięcie znaków (zredukowane modulo długość alfabetu)
+    offset: usize,
+    /// Alfabet, w którym przesuwane są znaki
+    alphabet: Alphabet
+}
 
 /// Metody dodatkowe związane z szyfrem cezara
 impl CeasarData {
-    /// Tworzy nową strukturę z informacjami szyfru cezara
+    /// Tworzy nową strukturę z informacjami szyfru cezara (alfabet łaciński z zachowaniem wielkości liter)
     /// 
     /// # Arguments
     /// 
-    /// * `offset` - przesunięcie znaków
-    pub fn new(offset: u8) -> CeasarData {
-        CeasarData(offset)
+    /// * `offset` - przesunięcie znaków (ujemne oznacza przesunięcie w lewo)
+    pub fn new(offset: impl Into<i64>) -> CeasarData {
+        CeasarData::with_alphabet(offset, Alphabet::latin_upper())
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru cezara dla podanego alfabetu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `offset` - przesunięcie znaków (ujemne oznacza przesunięcie w lewo)
+    /// * `alphabet` - alfabet, w którym przesuwane są znaki
+    pub fn with_alphabet(offset: impl Into<i64>, alphabet: Alphabet) -> CeasarData {
+        let offset: usize = offset.into().rem_euclid(alphabet.len() as i64) as usize;
+        CeasarData { offset, alphabet }
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru cezara, gdzie przesunięcie wyznacza litera
+    /// (np. `'D'` oznacza przesunięcie o 3)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `letter` - litera klucza
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NonAlphabeticKey`] - litera musi należeć do alfabetu łacińskiego
+    pub fn from_letter(letter: char) -> Result<CeasarData, Error> {
+        CeasarData::from_letter_with_alphabet(letter, Alphabet::latin_upper())
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru cezara dla podanego alfabetu, gdzie przesunięcie wyznacza litera
+    /// 
+    /// # Arguments
+    /// 
+    /// * `letter` - litera klucza
+    /// * `alphabet` - alfabet, w którym przesuwane są znaki
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NonAlphabeticKey`] - litera musi należeć do alfabetu
+    pub fn from_letter_with_alphabet(letter: char, alphabet: Alphabet) -> Result<CeasarData, Error> {
+        match alphabet.find(letter) {
+            Some(offset) => Ok(CeasarData { offset, alphabet }),
+            None => Err(Error::NonAlphabeticKey { character: letter, line: 1 })
+        }
+    }
+
+    /// Przesunięcie znaków (z zakresu od 0 do długości alfabetu)
+    pub fn offset(&self) -> usize {
+        self.offset
     }
 
     /// Przesuwa pojedynczy znak o podaną liczbę miejsc w alfabecie łacińskim
@@ -19,13 +72,22 @@ impl CeasarData {
     /// # Arguments
     /// 
     /// * `character` - znak do przesunięcia
-    /// * `offset` - wartość przesunięcia
-    pub fn shift_ascii(character: char, offset: i8) -> char {
-        let mut shift: i8 = 'A' as i8;
-        if character.is_ascii_lowercase() {
-            shift = 'a' as i8;
-        } 
-        ((((character as i8) + offset - shift) % 26) + shift) as u8 as char
+    /// * `offset` - wartość przesunięcia (ujemna oznacza przesunięcie w lewo)
+    pub fn shift_ascii(character: char, offset: i64) -> char {
+        let offset: u8 = offset.rem_euclid(26) as u8;
+        let base: u8 = match character {
+            'A'..='Z' => b'A',
+            'a'..='z' => b'a',
+            _ => return character
+        };
+        ((character as u8 - base + offset) % 26 + base) as char
+    }
+
+    /// Przesuwa pojedynczy znak o podaną liczbę miejsc w alfabecie szyfru
+    /// (znaki spoza alfabetu pozostają bez zmian)
+    fn shift(&self, character: char, offset: usize) -> char {
+        self.alphabet.translate(character, |index: usize| index + offset)
+                     .unwrap_or(character)
     }
 }
 
@@ -33,26 +95,13 @@ impl CeasarData {
 impl Cipher for CeasarData {
     fn encrypt(&self, data: &str) -> String{
         data.chars()
-            .map(|sign: char| {
-                if sign.is_ascii_alphabetic() { 
-                    CeasarData::shift_ascii(sign, self.0 as i8)
-                }
-                else { 
-                    sign 
-                } 
-            })
+            .map(|sign: char| self.shift(sign, self.offset))
             .collect::<String>()
     }
     fn decrypt(&self, data: &str) -> String{
+        let offset: usize = self.alphabet.len() - self.offset;
         data.chars()
-            .map(|sign| {
-                if sign.is_ascii_alphabetic() { 
-                    CeasarData::shift_ascii(sign, -(self.0 as i8))
-                }
-                else { 
-                    sign 
-                } 
-            })
+            .map(|sign: char| self.shift(sign, offset))
             .collect::<String>()
     }
-}
\ No newline at end of file
+}
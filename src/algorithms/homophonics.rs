@@ -1,70 +1,517 @@
-use crate::algorithms::Cipher;
-use crate::utils::Key;
-use rand::{Rng, prelude::ThreadRng};
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use crate::algorithms::{Cipher, Position, Unknown};
+use crate::utils::{Key, KeyHeader, KeyRule, Location};
+use crate::{Alphabet, Error};
+use rand::{Rng, RngCore, SeedableRng, prelude::SliceRandom, rngs::StdRng};
 
 /// Przechowuje dodatkowe infromacje potrzebne dla szyfru homofonicznego
-pub struct HomophonicData(Key);
+pub struct HomophonicData {
+    /// Klucz szyfrujący
+    code: Key,
+    /// Alfabet tekstu jawnego (kolejne pozycje klucza odpowiadają kolejnym symbolom)
+    alphabet: Alphabet,
+    /// Indeks odwrotny: homofon i indeks odpowiadającego mu symbolu alfabetu
+    homophones: HashMap<char, usize>,
+    /// Sposób obsługi znaków spoza klucza przy odszyfrowywaniu
+    unknown: Unknown,
+    /// Sposób obsługi znaków tekstu jawnego bez homofonów przy szyfrowaniu
+    unmapped: Unknown,
+    /// Czy znaki tekstu jawnego dopasowywane są tylko dokładnie (klucz z symbolami),
+    /// czy również po zmianie wielkości litery
+    exact: bool,
+    /// Generator liczb losowych wykorzystywany przy wyborze homofonów
+    rng: RefCell<Box<dyn RngCore>>,
+    /// Sposób wyboru homofonu dla kolejnych znaków
+    strategy: HomophoneStrategy,
+    /// Stan wyboru homofonów dla poszczególnych symboli alfabetu
+    selection: RefCell<Selection>,
+    /// Symbole puste (usuwane przy odszyfrowywaniu)
+    nulls: Vec<char>,
+    /// Sposób wstawiania symboli pustych przy szyfrowaniu
+    null_placement: NullPlacement
+}
+
+/// Do określania w jaki sposób wybierać homofon spośród dostępnych dla danego symbolu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HomophoneStrategy {
+    /// Losowo z rozkładem jednostajnym
+    #[default]
+    Random,
+    /// Po kolei dla każdego symbolu tekstu jawnego
+    RoundRobin,
+    /// Najdawniej użyty homofon danego symbolu (remisy rozstrzygane losowo)
+    LeastRecentlyUsed,
+    /// Wyznaczany na podstawie pozycji znaku i ziarna (ten sam tekst daje zawsze ten sam szyfrogram)
+    Deterministic { seed: u64 }
+}
+
+/// Do określania gdzie wstawiać symbole puste przy szyfrowaniu
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NullPlacement {
+    /// Nie wstawiaj symboli pustych
+    #[default]
+    None,
+    /// Po każdym znaku z podanym prawdopodobieństwem (od 0 do 1)
+    Random { rate: f64 },
+    /// Po co `every` znaku tekstu jawnego
+    Interval { every: usize }
+}
+
+/// Parametry generowania klucza szyfru homofonicznego
+#[derive(Debug, Clone)]
+pub struct HomophonicKeyParams {
+    /// Alfabet tekstu jawnego (każdy symbol otrzymuje osobną pozycję klucza)
+    pub alphabet: Alphabet,
+    /// Znaki, z których wybierane są homofony (białe znaki są pomijane)
+    pub symbols: Vec<char>,
+    /// Maksymalna ilość homofonów na pozycję
+    pub max_homophones: usize,
+    /// Ilość symboli pustych (zapisywanych w nagłówku klucza)
+    pub nulls: usize,
+    /// Sposób reprezentacji klucza
+    pub rule: KeyRule
+}
+
+/// Domyślne parametry: alfabet łaciński (A-Z + a-z), drukowalne znaki ASCII, do 3 homofonów na pozycję, bez symboli pustych
+impl Default for HomophonicKeyParams {
+    fn default() -> HomophonicKeyParams {
+        HomophonicKeyParams { 
+            alphabet: Alphabet::latin_mixed(), 
+            symbols: ('!'..='~').collect(), 
+            max_homophones: 3, 
+            nulls: 0,
+            rule: KeyRule::Row 
+        }
+    }
+}
+
+/// Stan wyboru homofonów (wykorzystywany przez strategie zależne od historii)
+#[derive(Default)]
+struct Selection {
+    /// Indeks następnego homofonu dla każdego symbolu alfabetu
+    next: Vec<usize>,
+    /// Chwila ostatniego użycia każdego homofonu danego symbolu (0 - nieużyty)
+    last_used: Vec<Vec<usize>>,
+    /// Licznik wyborów
+    tick: usize
+}
 
 /// Metody dodatkowe związane z szyfrem homofonicznym
 impl HomophonicData {
-    /// Tworzy nową strukturę z informacjami szyfru homofonicznego
+    /// Tworzy nową strukturę z informacjami szyfru homofonicznego dla alfabetu z nagłówka klucza
+    /// (domyślnie alfabet łaciński A-Z + a-z), a dla klucza [`KeyRule::Labeled`] dla alfabetu złożonego z symboli podanych w kluczu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyTooShort`] - klucz musi posiadać co najmniej tyle pozycji co alfabet (52 dla alfabetu łacińskiego)
+    /// * [`Error::NonUniqueKey`] - klucz musi posiadać unikalne znaki
+    /// * [`Error::EmptyAlphabet`] - klucz [`KeyRule::Labeled`] nie posiada żadnej pozycji
+    pub fn new(code: Key) -> Result<HomophonicData, Error> {
+        let alphabet: Alphabet = match code.rule {
+            KeyRule::Labeled => Alphabet::new(code.labels.clone())?,
+            _ => code.alphabet().cloned().unwrap_or_else(Alphabet::latin_mixed)
+        };
+        HomophonicData::with_alphabet(code, alphabet)
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru homofonicznego dla podanego alfabetu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// * `alphabet` - alfabet tekstu jawnego
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyTooShort`] - klucz musi posiadać co najmniej tyle pozycji co alfabet symboli
+    ///   (nie dotyczy klucza [`KeyRule::Labeled`], w którym symbole bez pozycji nie mają homofonów)
+    /// * [`Error::NonUniqueKey`] - klucz musi posiadać unikalne znaki
+    /// * [`Error::NonAlphabeticKey`] - symbol klucza [`KeyRule::Labeled`] nie należy do alfabetu
+    /// * [`Error::NullCollision`] - symbol pusty z nagłówka klucza jest jednocześnie homofonem
+    pub fn with_alphabet(code: Key, alphabet: Alphabet) -> Result<HomophonicData, Error> {
+        if code.rule != KeyRule::Labeled && code.data.len() < alphabet.len() { 
+            return Err(Error::KeyTooShort { expected: alphabet.len(), found: code.data.len() }) 
+        }
+        if let Some(duplicate) = code.validate().duplicates.first() {
+            let location: &Location = &duplicate.locations[1];
+            let line: usize = match code.rule {
+                KeyRule::Row | KeyRule::Labeled => location.row,
+                KeyRule::Column => location.column
+            };
+            return Err(Error::NonUniqueKey { character: duplicate.character, line })
+        }
+        // pozycje klucza z symbolami układane są w kolejności alfabetu
+        let exact: bool = code.rule == KeyRule::Labeled;
+        let code: Key = match code.rule {
+            KeyRule::Labeled => {
+                let mut data: Vec<String> = vec![String::new(); alphabet.len()];
+                for (line, (&label, vec)) in code.labels.iter().zip(&code.data).enumerate() {
+                    let index: usize = alphabet.index(label)
+                                               .ok_or(Error::NonAlphabeticKey { character: label, line: line + 1 })?;
+                    data[index] = vec.clone();
+                }
+                Key { data, locations: Vec::new(), ..code }
+            },
+            _ => code
+        };
+        let mut homophones: HashMap<char, usize> = HashMap::new();
+        for (index, vec) in code.data.iter().take(alphabet.len()).enumerate() {
+            for sign in vec.chars() {
+                homophones.entry(sign).or_insert(index);
+            }
+        }
+        let rng: RefCell<Box<dyn RngCore>> = RefCell::new(Box::new(StdRng::from_entropy()));
+        let nulls: Vec<char> = code.nulls().to_vec();
+        HomophonicData { code, alphabet, homophones, unknown: Unknown::default(), unmapped: Unknown::Keep, exact, rng, 
+                         strategy: HomophoneStrategy::default(), selection: RefCell::default(), 
+                         nulls: Vec::new(), null_placement: NullPlacement::default() }.null_symbols(nulls)
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru homofonicznego (alfabet wybierany jak w [`HomophonicData::new`])
+    /// korzystającą z podanego generatora liczb losowych (np. `StdRng::seed_from_u64`)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// * `rng` - generator liczb losowych
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`HomophonicData::new`]
+    pub fn with_rng(code: Key, rng: impl RngCore + 'static) -> Result<HomophonicData, Error> {
+        Ok(HomophonicData::new(code)?.rng(rng))
+    }
+
+    /// Tworzy losowy klucz, który zawsze jest poprawnym kluczem szyfru homofonicznego dla podanego alfabetu
+    /// (każdy symbol alfabetu otrzymuje od 1 do `max_homophones` unikalnych homofonów)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - parametry generowania klucza
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::DuplicateSymbol`] - pula znaków zawiera powtarzający się znak
+    /// * [`Error::InsufficientSymbols`] - pula znaków jest mniejsza niż alfabet wraz z symbolami pustymi
+    pub fn generate_key(params: &HomophonicKeyParams) -> Result<Key, Error> {
+        HomophonicData::generate_key_with_rng(params, &mut rand::thread_rng())
+    }
+
+    /// Tworzy losowy klucz szyfru homofonicznego korzystając z podanego generatora liczb losowych
     /// 
     /// # Arguments
     /// 
-    /// * `code` - klucz do wykorzystania (musi spełniać warunki[^1][^2])
+    /// * `params` - parametry generowania klucza
+    /// * `rng` - generator liczb losowych
     /// 
     /// # Errors
     /// 
-    /// * [^1]Klucz musi posiadać co najmniej 52 znaki w linii bądź kolumnie
-    /// * [^2]Klucz musi posiadać unikalne znaki
-    pub fn new(code: Key) -> Result<HomophonicData, Box<dyn std::error::Error>> {
-        if code.data.len() < 52 { 
-            return Err("Invalid code! Valid code must contain at least 52 characters".into()) 
-        } else if !code.unique {
-            return Err("Invalid code! Valid code must contain unique characters".into())
+    /// Patrz [`HomophonicData::generate_key`]
+    pub fn generate_key_with_rng(params: &HomophonicKeyParams, rng: &mut impl Rng) -> Result<Key, Error> {
+        let mut symbols: Vec<char> = params.symbols.iter()
+                                                   .copied()
+                                                   .filter(|c: &char| !c.is_whitespace())
+                                                   .collect();
+        let mut sorted: Vec<char> = symbols.clone();
+        sorted.sort_unstable();
+        if let Some(pair) = sorted.windows(2).find(|pair: &&[char]| pair[0] == pair[1]) {
+            return Err(Error::DuplicateSymbol { character: pair[0] });
+        }
+        let positions: usize = params.alphabet.len();
+        if symbols.len() < positions + params.nulls {
+            return Err(Error::InsufficientSymbols { required: positions + params.nulls, found: symbols.len() });
+        }
+
+        symbols.shuffle(rng);
+        let nulls: Vec<char> = symbols.drain(..params.nulls).collect();
+        let mut data: Vec<String> = symbols.drain(..positions).map(String::from).collect();
+        for homophones in &mut data {
+            let amount: usize = rng.gen_range(0..=params.max_homophones.saturating_sub(1).min(symbols.len()));
+            homophones.extend(symbols.drain(..amount));
+        }
+        let mut key: Key = Key::new(data, KeyRule::Row);
+        if !nulls.is_empty() {
+            key = key.header(KeyHeader::new().nulls(nulls));
+        }
+        match params.rule {
+            KeyRule::Labeled => key.label(&params.alphabet),
+            rule => Ok(key.rule(rule))
         }
-        Ok(HomophonicData(code))
+    }
+
+    /// Ustawia generator liczb losowych wykorzystywany przy wyborze homofonów
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rng` - generator liczb losowych
+    pub fn rng(self, rng: impl RngCore + 'static) -> HomophonicData {
+        *self.rng.borrow_mut() = Box::new(rng);
+        self
+    }
+
+    /// Ustawia sposób wyboru homofonów (czyści stan poprzednich wyborów)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strategy` - sposób wyboru homofonów
+    pub fn strategy(mut self, strategy: HomophoneStrategy) -> HomophonicData {
+        self.strategy = strategy;
+        self.selection = RefCell::default();
+        self
+    }
+
+    /// Zwraca sposób wyboru homofonów
+    pub fn homophone_strategy(&self) -> HomophoneStrategy {
+        self.strategy
+    }
+
+    /// Zwraca alfabet tekstu jawnego
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// Ustawia symbole puste (zastępując zadeklarowane w nagłówku klucza)
+    /// 
+    /// Znaki tekstu jawnego przepisywane bez zmian, które są symbolami pustymi, zostaną usunięte przy odszyfrowywaniu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `nulls` - symbole puste
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NullCollision`] - symbol pusty jest jednocześnie homofonem bądź białym znakiem
+    pub fn null_symbols(mut self, nulls: Vec<char>) -> Result<HomophonicData, Error> {
+        if let Some(&character) = nulls.iter().find(|&c: &&char| self.homophones.contains_key(c) || c.is_whitespace()) {
+            return Err(Error::NullCollision { character });
+        }
+        self.nulls = nulls;
+        Ok(self)
+    }
+
+    /// Ustawia sposób wstawiania symboli pustych przy szyfrowaniu (bez symboli pustych nie wstawia niczego)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `placement` - sposób wstawiania symboli pustych
+    pub fn null_placement(mut self, placement: NullPlacement) -> HomophonicData {
+        self.null_placement = placement;
+        self
+    }
+
+    /// Ustawia sposób obsługi znaków tekstu jawnego, które nie mają homofonów (domyślnie przepisywane bez zmian,
+    /// [`Unknown::Reject`] powoduje błąd w [`Cipher::try_encrypt`]; znak będący homofonem bądź symbolem pustym
+    /// nigdy nie jest przepisywany)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `unmapped` - sposób obsługi znaków bez homofonów
+    pub fn unmapped(mut self, unmapped: Unknown) -> HomophonicData {
+        self.unmapped = unmapped;
+        self
+    }
+
+    /// Ustawia sposób obsługi znaków szyfrogramu, których nie ma w kluczu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `unknown` - sposób obsługi nieznanych znaków
+    pub fn unknown(mut self, unknown: Unknown) -> HomophonicData {
+        self.unknown = unknown;
+        self
+    }
+
+    /// Wybiera indeks homofonu dla symbolu alfabetu zgodnie z ustawioną strategią
+    /// 
+    /// # Arguments
+    /// 
+    /// * `group` - indeks symbolu alfabetu (pozycja klucza)
+    /// * `count` - ilość homofonów danego symbolu (większa od 0)
+    /// * `position` - pozycja znaku w tekście
+    pub(crate) fn pick(&self, group: usize, count: usize, position: usize) -> usize {
+        match self.strategy {
+            HomophoneStrategy::Random => self.rng.borrow_mut().gen_range(0..count),
+            HomophoneStrategy::Deterministic { seed } => {
+                (mix(seed ^ mix(position as u64 ^ mix(group as u64))) % count as u64) as usize
+            },
+            HomophoneStrategy::RoundRobin => {
+                let mut selection: RefMut<Selection> = self.selection.borrow_mut();
+                if selection.next.len() <= group {
+                    selection.next.resize(group + 1, 0);
+                }
+                let index: usize = selection.next[group] % count;
+                selection.next[group] = index + 1;
+                index
+            },
+            HomophoneStrategy::LeastRecentlyUsed => {
+                let mut selection: RefMut<Selection> = self.selection.borrow_mut();
+                if selection.last_used.len() <= group {
+                    selection.last_used.resize(group + 1, Vec::new());
+                }
+                selection.tick += 1;
+                let tick: usize = selection.tick;
+                let last_used: &mut Vec<usize> = &mut selection.last_used[group];
+                last_used.resize(count, 0);
+                let oldest: usize = *last_used.iter().min().unwrap();
+                let candidates: Vec<usize> = (0..count).filter(|&i: &usize| last_used[i] == oldest).collect();
+                let index: usize = candidates[self.rng.borrow_mut().gen_range(0..candidates.len())];
+                last_used[index] = tick;
+                index
+            }
+        }
+    }
+
+    /// Szyfruje ciąg znaków, którego pierwszy znak znajduje się na podanej pozycji tekstu,
+    /// obsługując znaki bez homofonów zgodnie z [`HomophonicData::unmapped_sign`] (białe znaki są zawsze przepisywane)
+    fn encode(&self, data: &str, start: usize, lenient: bool) -> Result<String, Error> {
+        let mut output: String = String::with_capacity(data.len());
+        for (position, sign) in data.chars().enumerate() {
+            if sign.is_whitespace() {
+                output.push(sign);
+                continue;
+            }
+            match self.substitute(sign, start + position) {
+                Some(homophone) => output.push(homophone),
+                None => output.push(self.unmapped_sign(sign, position, lenient)?)
+            }
+            if let Some(null) = self.null_after(start + position) {
+                output.push(null);
+            }
+        }
+        Ok(output)
+    }
+
+    /// Wybiera homofon dla znaku tekstu jawnego (`None` dla znaku bez homofonów)
+    pub(crate) fn substitute(&self, sign: char, position: usize) -> Option<char> {
+        let index: usize = match self.exact {
+            true => self.alphabet.index(sign)?,
+            false => self.alphabet.find(sign)?
+        };
+        let char_key: &String = &self.code.data[index];
+        let count: usize = char_key.chars().count();
+        if count == 0 {
+            return None;
+        }
+        char_key.chars().nth(self.pick(index, count, position))
+    }
+
+    /// Zwraca symbol pusty do wstawienia po znaku na podanej pozycji tekstu (zgodnie z [`NullPlacement`])
+    pub(crate) fn null_after(&self, position: usize) -> Option<char> {
+        if self.nulls.is_empty() {
+            return None;
+        }
+        match self.null_placement {
+            NullPlacement::None => None,
+            NullPlacement::Random { rate } => {
+                let mut rng: RefMut<Box<dyn RngCore>> = self.rng.borrow_mut();
+                match rng.gen_bool(rate.clamp(0.0, 1.0)) {
+                    true => Some(self.nulls[rng.gen_range(0..self.nulls.len())]),
+                    false => None
+                }
+            },
+            NullPlacement::Interval { every } if every > 0 && (position + 1).is_multiple_of(every) => {
+                Some(self.nulls[((position + 1) / every - 1) % self.nulls.len()])
+            },
+            NullPlacement::Interval { .. } => None
+        }
+    }
+
+    /// Zwraca symbol alfabetu odpowiadający homofonowi (`None` dla znaku spoza klucza)
+    pub(crate) fn plaintext(&self, sign: char) -> Option<char> {
+        self.homophones.get(&sign).map(|&index: &usize| self.alphabet.symbol(index))
+    }
+
+    /// Czy znak jest symbolem pustym
+    pub(crate) fn is_null(&self, sign: char) -> bool {
+        self.nulls.contains(&sign)
+    }
+
+    /// Zwraca znak wstawiany w miejsce znaku bez homofonów (zgodnie ze sposobem obsługi takich znaków)
+    /// 
+    /// Znak, który przy odszyfrowywaniu zostałby odczytany jako homofon bądź symbol pusty, jest zgłaszany jako błąd,
+    /// a w trybie bez błędów zastępowany jest przez [`char::REPLACEMENT_CHARACTER`]
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sign` - znak bez homofonów
+    /// * `position` - pozycja znaku w tekście
+    /// * `lenient` - czy zamiast zgłaszać błąd należy zastąpić znak
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::UnknownSymbol`] - znak nie może zostać przepisany
+    pub(crate) fn unmapped_sign(&self, sign: char, position: usize, lenient: bool) -> Result<char, Error> {
+        let unmapped: Unknown = match lenient {
+            true => self.unmapped.lenient(),
+            false => self.unmapped
+        };
+        let copied: char = unmapped.handle(sign, position)?;
+        match self.plaintext(copied).is_some() || self.is_null(copied) {
+            true if lenient => Unknown::Replace(char::REPLACEMENT_CHARACTER).handle(sign, position),
+            true => Err(Error::UnknownSymbol { character: sign, position }),
+            false => Ok(copied)
+        }
+    }
+
+    /// Zwraca sposoby obsługi znaków bez homofonów (przy szyfrowaniu) i znaków spoza klucza (przy odszyfrowywaniu)
+    pub(crate) fn policies(&self) -> (Unknown, Unknown) {
+        (self.unmapped, self.unknown)
+    }
+
+    /// Odszyfrowuje ciąg znaków obsługując nieznane znaki w podany sposób (symbole puste są pomijane)
+    fn decode(&self, data: &str, unknown: Unknown) -> Result<String, Error> {
+        data.chars()
+            .enumerate()
+            .filter(|(_, sign): &(usize, char)| !self.nulls.contains(sign))
+            .map(|(position, sign): (usize, char)| {
+                if sign.is_whitespace() {
+                    return Ok(sign);
+                }
+                match self.homophones.get(&sign) {
+                    Some(&index) => Ok(self.alphabet.symbol(index)),
+                    None => unknown.handle(sign, position)
+                }
+            })
+            .collect::<Result<String, Error>>()
     }
 }
 
 /// Implementacja metod szyfrujących
 impl Cipher for HomophonicData {
     fn encrypt(&self, data: &str) -> String { 
-        let mut rng: ThreadRng = rand::thread_rng();
-        data.chars()
-            .map(|sign: char| {
-                if !sign.is_whitespace() && sign.is_ascii() { 
-                    let char_key: &String;
-                    if sign.is_ascii_lowercase() {
-                        char_key = &self.0.data[((sign as usize - 'a' as usize) % 52 + 26)]
-                    } else {
-                        char_key = &self.0.data[((sign as usize - 'A' as usize) % 52)]
-                    }
-                    char_key.chars()
-                     .nth(rng.gen_range(0..char_key.len()))
-                     .unwrap()
-                } else { 
-                    sign 
-                } 
-            })
-            .collect::<String>()
+        self.encode(data, 0, true).unwrap_or_default()
+    }
+    fn try_encrypt(&self, data: &str) -> Result<String, Error> {
+        self.encode(data, 0, false)
+    }
+    fn encrypt_at(&self, data: &str, position: &mut Position) -> String {
+        let output: String = self.encode(data, position.characters, true).unwrap_or_default();
+        position.characters += data.chars().count();
+        output
+    }
+    fn try_encrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        let output: String = self.encode(data, position.characters, false)
+                                 .map_err(|error: Error| error.shifted(position.characters))?;
+        position.characters += data.chars().count();
+        Ok(output)
     }
     fn decrypt(&self, data: &str) -> String { 
-        data.chars()
-            .map(|sign: char| {
-                if !sign.is_whitespace() && sign.is_ascii() { 
-                    let index = self.0.data.iter()
-                                               .position(|v: &String| v.contains(sign))
-                                               .unwrap() as u8;
-                    if index < 26 {
-                        ('A' as u8 + index) as char
-                    } else {
-                        ('a' as u8 + index % 26) as char
-                    }
-                } else { 
-                    sign 
-                } 
-            })
-            .collect::<String>()
+        self.decode(data, self.unknown.lenient()).unwrap_or_default()
     }
-}
\ No newline at end of file
+    fn try_decrypt(&self, data: &str) -> Result<String, Error> {
+        self.decode(data, self.unknown)
+    }
+}
+
+/// Funkcja mieszająca (SplitMix64) wykorzystywana przez [`HomophoneStrategy::Deterministic`]
+fn mix(value: u64) -> u64 {
+    let mut z: u64 = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::{fmt, fs, io};
+use crate::algorithms::{Cipher, HomophonicData, Position, Unknown};
+use crate::utils::translation_path;
+use crate::Error;
+
+/// Książka kodowa nomenklatora: słowa (bądź wyrażenia) i przypisane im grupy kodowe
+/// 
+/// Plik książki kodowej (rozszerzenie `.cbook`) zawiera linijki postaci `słowo: grupa grupa ...`
+/// (np. `Warszawa: §1 §2`), puste linijki oraz linijki zaczynające się od `#` są pomijane
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Codebook {
+    /// Słowa i ich grupy kodowe (każda grupa jest alternatywnym zapisem słowa)
+    entries: Vec<(String, Vec<String>)>
+}
+
+/// Metody książki kodowej
+impl Codebook {
+    /// Tworzy książkę kodową z podanych słów i grup kodowych
+    /// 
+    /// # Arguments
+    /// 
+    /// * `entries` - słowa i ich grupy kodowe
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::MalformedCodebookLine`] - puste słowo bądź słowo bez grup kodowych (numer pozycji liczony od 1)
+    /// * [`Error::DuplicateCodeword`] - słowo powtarza się (numer pozycji liczony od 1)
+    /// * [`Error::AmbiguousCodeGroup`] - grupa kodowa zawiera biały znak, powtarza się bądź jest prefiksem innej grupy
+    pub fn new(entries: Vec<(String, Vec<String>)>) -> Result<Codebook, Error> {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for (index, (word, groups)) in entries.iter().enumerate() {
+            if word.trim().is_empty() || groups.is_empty() {
+                return Err(Error::MalformedCodebookLine { line: index + 1 });
+            }
+            if seen.insert(word.as_str(), index).is_some() {
+                return Err(Error::DuplicateCodeword { word: word.clone(), line: index + 1 });
+            }
+        }
+        let mut sorted: Vec<&String> = entries.iter().flat_map(|(_, groups): &(String, Vec<String>)| groups).collect();
+        if let Some(group) = sorted.iter().find(|g: &&&String| g.is_empty() || g.chars().any(char::is_whitespace)) {
+            return Err(Error::AmbiguousCodeGroup { group: group.to_string() });
+        }
+        // po posortowaniu grupa będąca prefiksem innej grupy znajduje się bezpośrednio przed grupą, którą rozpoczyna
+        sorted.sort_unstable();
+        if let Some(pair) = sorted.windows(2).find(|pair: &&[&String]| pair[1].starts_with(pair[0].as_str())) {
+            return Err(Error::AmbiguousCodeGroup { group: pair[0].to_string() });
+        }
+        Ok(Codebook { entries })
+    }
+
+    /// Zczytuje książkę kodową z pliku
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyIo`] - błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
+    /// * błędy formatu (patrz [`Codebook::parse`])
+    pub fn read(path: &str) -> Result<Codebook, Error> {
+        let str: String = fs::read_to_string(path)
+            .map_err(|source: io::Error| Error::KeyIo { path: path.to_string(), source })?;
+        Codebook::parse(&str)
+    }
+
+    /// Zczytuje książkę kodową z pliku wyszukiwanego w katalogach kluczy (patrz [`crate::utils::search_path`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`Codebook::read`]
+    pub fn read_translation(path: &str) -> Result<Codebook, Error> {
+        Codebook::read(&translation_path(path))
+    }
+
+    /// Tworzy książkę kodową z tekstu w formacie pliku książki kodowej
+    /// 
+    /// # Arguments
+    /// 
+    /// * `str` - zawartość książki kodowej
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::MalformedCodebookLine`] - linijka nie ma postaci `słowo: grupy kodowe`
+    /// * [`Error::DuplicateCodeword`] - słowo powtarza się w książce kodowej
+    /// * [`Error::AmbiguousCodeGroup`] - grupa kodowa powtarza się bądź jest prefiksem innej grupy
+    pub fn parse(str: &str) -> Result<Codebook, Error> {
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        let mut lines: Vec<usize> = Vec::new();
+        for (index, line) in str.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, groups): (&str, &str) = line.split_once(':')
+                                                   .ok_or(Error::MalformedCodebookLine { line: index + 1 })?;
+            entries.push((word.trim().to_string(), groups.split_whitespace().map(String::from).collect()));
+            lines.push(index + 1);
+        }
+        // numery pozycji zamieniane są na numery linijek pliku
+        Codebook::new(entries).map_err(|error: Error| match error {
+            Error::MalformedCodebookLine { line } => Error::MalformedCodebookLine { line: lines[line - 1] },
+            Error::DuplicateCodeword { word, line } => Error::DuplicateCodeword { word, line: lines[line - 1] },
+            other => other
+        })
+    }
+
+    /// Zwraca grupy kodowe podanego słowa (`None` dla słowa spoza książki kodowej)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `word` - słowo do wyszukania
+    pub fn groups(&self, word: &str) -> Option<&[String]> {
+        self.entries.iter()
+                    .find(|(entry, _): &&(String, Vec<String>)| entry == word)
+                    .map(|(_, groups): &(String, Vec<String>)| groups.as_slice())
+    }
+
+    /// Ilość słów w książce kodowej
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Czy książka kodowa jest pusta
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Zapis w formacie pliku książki kodowej
+impl fmt::Display for Codebook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (word, groups) in &self.entries {
+            writeln!(f, "{}: {}", word, groups.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Przechowuje dodatkowe infromacje potrzebne dla nomenklatora (szyfru homofonicznego z książką kodową)
+/// 
+/// Słowa z książki kodowej zastępowane są w całości jedną z ich grup kodowych, pozostałe znaki szyfrowane są
+/// homofonami. Grupy kodowe nie mogą zaczynać się od homofonu ani symbolu pustego, a znaki przepisywane bez zmian
+/// nie mogą rozpoczynać grupy kodowej, więc szyfrogram można odczytać jednoznacznie
+pub struct NomenclatorData {
+    /// Szyfr homofoniczny dla znaków spoza książki kodowej
+    homophonic: HomophonicData,
+    /// Książka kodowa
+    codebook: Codebook,
+    /// Znaki kolejnych słów książki kodowej
+    words: Vec<Vec<char>>,
+    /// Indeksy słów według pierwszego znaku (od najdłuższego słowa)
+    by_letter: HashMap<char, Vec<usize>>,
+    /// Grupy kodowe (znaki i indeks słowa) według pierwszego znaku
+    by_symbol: HashMap<char, Vec<(Vec<char>, usize)>>
+}
+
+/// Metody dodatkowe związane z nomenklatorem
+impl NomenclatorData {
+    /// Tworzy nową strukturę z informacjami nomenklatora (ustawienia szyfru homofonicznego,
+    /// np. strategia wyboru homofonów, dotyczą również wyboru grup kodowych)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `homophonic` - szyfr homofoniczny dla znaków spoza książki kodowej
+    /// * `codebook` - książka kodowa
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::AmbiguousCodeGroup`] - grupa kodowa zaczyna się od homofonu bądź symbolu pustego
+    pub fn new(homophonic: HomophonicData, codebook: Codebook) -> Result<NomenclatorData, Error> {
+        let words: Vec<Vec<char>> = codebook.entries.iter()
+                                                    .map(|(word, _): &(String, Vec<String>)| word.chars().collect())
+                                                    .collect();
+        let mut by_letter: HashMap<char, Vec<usize>> = HashMap::new();
+        let mut by_symbol: HashMap<char, Vec<(Vec<char>, usize)>> = HashMap::new();
+        for (index, (_, groups)) in codebook.entries.iter().enumerate() {
+            by_letter.entry(words[index][0]).or_default().push(index);
+            for group in groups {
+                let first: char = group.chars().next().expect("Checked by Codebook::new");
+                if homophonic.plaintext(first).is_some() || homophonic.is_null(first) {
+                    return Err(Error::AmbiguousCodeGroup { group: group.clone() });
+                }
+                by_symbol.entry(first).or_default().push((group.chars().collect(), index));
+            }
+        }
+        // najpierw dopasowywane są najdłuższe słowa
+        for indices in by_letter.values_mut() {
+            indices.sort_by_key(|&index: &usize| std::cmp::Reverse(words[index].len()));
+        }
+        Ok(NomenclatorData { homophonic, codebook, words, by_letter, by_symbol })
+    }
+
+    /// Zwraca książkę kodową
+    pub fn codebook(&self) -> &Codebook {
+        &self.codebook
+    }
+
+    /// Zwraca indeks najdłuższego słowa książki kodowej zaczynającego się na podanej pozycji
+    /// (słowo musi zaczynać się i kończyć na granicy wyrazu)
+    fn codeword(&self, chars: &[char], start: usize) -> Option<usize> {
+        if start > 0 && chars[start - 1].is_alphanumeric() {
+            return None;
+        }
+        self.by_letter.get(&chars[start])?
+            .iter()
+            .copied()
+            .find(|&index: &usize| {
+                let word: &[char] = &self.words[index];
+                let end: usize = start + word.len();
+                chars[start..].starts_with(word) && (end == chars.len() || !chars[end].is_alphanumeric())
+            })
+    }
+
+    /// Zwraca indeks słowa i długość grupy kodowej zaczynającej się na podanej pozycji szyfrogramu
+    fn group_at(&self, chars: &[char], start: usize) -> Option<(usize, usize)> {
+        self.by_symbol.get(&chars[start])?
+            .iter()
+            .find(|(group, _): &&(Vec<char>, usize)| chars[start..].starts_with(group))
+            .map(|(group, index): &(Vec<char>, usize)| (*index, group.len()))
+    }
+
+    /// Obsługuje znak bez homofonów jak [`HomophonicData::unmapped_sign`], traktując dodatkowo
+    /// pierwsze znaki grup kodowych tak jak homofony
+    fn unmapped_sign(&self, sign: char, position: usize, lenient: bool) -> Result<char, Error> {
+        let copied: char = self.homophonic.unmapped_sign(sign, position, lenient)?;
+        match self.by_symbol.contains_key(&copied) {
+            true if lenient => Ok(char::REPLACEMENT_CHARACTER),
+            true => Err(Error::UnknownSymbol { character: sign, position }),
+            false => Ok(copied)
+        }
+    }
+
+    /// Szyfruje ciąg znaków, którego pierwszy znak znajduje się na podanej pozycji tekstu,
+    /// zastępując słowa książki kodowej (najdłuższe dopasowanie) i obsługując znaki bez homofonów zgodnie z [`NomenclatorData::unmapped_sign`]
+    fn encode(&self, data: &str, start: usize, lenient: bool) -> Result<String, Error> {
+        let chars: Vec<char> = data.chars().collect();
+        let mut output: String = String::with_capacity(data.len());
+        let mut position: usize = 0;
+        while position < chars.len() {
+            let sign: char = chars[position];
+            let length: usize = match self.codeword(&chars, position) {
+                Some(index) => {
+                    // grupy kodowe słów wybierane są po grupach homofonów wszystkich symboli alfabetu
+                    let groups: &[String] = &self.codebook.entries[index].1;
+                    let group: usize = self.homophonic.pick(self.homophonic.alphabet().len() + index, groups.len(), start + position);
+                    output.push_str(&groups[group]);
+                    self.words[index].len()
+                },
+                None if sign.is_whitespace() => {
+                    output.push(sign);
+                    position += 1;
+                    continue;
+                },
+                None => {
+                    match self.homophonic.substitute(sign, start + position) {
+                        Some(homophone) => output.push(homophone),
+                        None => output.push(self.unmapped_sign(sign, position, lenient)?)
+                    }
+                    1
+                }
+            };
+            if let Some(null) = self.homophonic.null_after(start + position + length - 1) {
+                output.push(null);
+            }
+            position += length;
+        }
+        Ok(output)
+    }
+
+    /// Odszyfrowuje ciąg znaków obsługując nieznane znaki w podany sposób (symbole puste są pomijane)
+    fn decode(&self, data: &str, unknown: Unknown) -> Result<String, Error> {
+        let chars: Vec<char> = data.chars().collect();
+        let mut output: String = String::with_capacity(data.len());
+        let mut position: usize = 0;
+        while position < chars.len() {
+            let sign: char = chars[position];
+            if sign.is_whitespace() {
+                output.push(sign);
+            } else if let Some(symbol) = self.homophonic.plaintext(sign) {
+                output.push(symbol);
+            } else if let Some((index, length)) = self.group_at(&chars, position) {
+                output.push_str(&self.codebook.entries[index].0);
+                position += length;
+                continue;
+            } else if !self.homophonic.is_null(sign) {
+                output.push(unknown.handle(sign, position)?);
+            }
+            position += 1;
+        }
+        Ok(output)
+    }
+}
+
+/// Implementacja metod szyfrujących
+impl Cipher for NomenclatorData {
+    fn encrypt(&self, data: &str) -> String {
+        self.encode(data, 0, true).unwrap_or_default()
+    }
+    fn try_encrypt(&self, data: &str) -> Result<String, Error> {
+        self.encode(data, 0, false)
+    }
+    fn encrypt_at(&self, data: &str, position: &mut Position) -> String {
+        let output: String = self.encode(data, position.characters, true).unwrap_or_default();
+        position.characters += data.chars().count();
+        output
+    }
+    fn try_encrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        let output: String = self.encode(data, position.characters, false)
+                                 .map_err(|error: Error| error.shifted(position.characters))?;
+        position.characters += data.chars().count();
+        Ok(output)
+    }
+    fn decrypt(&self, data: &str) -> String {
+        self.decode(data, self.homophonic.policies().1.lenient()).unwrap_or_default()
+    }
+    fn try_decrypt(&self, data: &str) -> Result<String, Error> {
+        self.decode(data, self.homophonic.policies().1)
+    }
+}
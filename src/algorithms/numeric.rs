@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+use std::{fmt, fs, io};
+use crate::algorithms::{Cipher, HomophoneStrategy, HomophonicData, Position, Unknown};
+use crate::utils::{allocate, translation_path, Key, KeyRule};
+use crate::{Alphabet, Error, Language};
+use rand::{Rng, RngCore, prelude::SliceRandom};
+
+/// Maksymalna ilość cyfr kodu liczbowego
+pub const MAX_CODE_WIDTH: usize = 4;
+
+/// Początek obszaru prywatnego Unicode, w którym kody liczbowe reprezentowane są jako homofony
+const CODE_BASE: u32 = 0xF0000;
+
+/// Klucz szyfru homofonicznego z kodami liczbowymi (np. 00-99 bądź 000-999)
+/// 
+/// Plik klucza (rozszerzenie `.nkey`) zawiera linijki postaci `symbol: kody` (np. `A: 07 31 64`),
+/// wszystkie kody zapisane są tą samą ilością cyfr
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumericKey {
+    /// Symbole tekstu jawnego
+    labels: Vec<char>,
+    /// Kody kolejnych symboli
+    codes: Vec<Vec<u32>>,
+    /// Ilość cyfr każdego kodu
+    width: usize
+}
+
+/// Metody klucza z kodami liczbowymi
+impl NumericKey {
+    /// Tworzy klucz z kodami liczbowymi
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - symbole tekstu jawnego
+    /// * `codes` - kody kolejnych symboli
+    /// * `width` - ilość cyfr każdego kodu (od 1 do [`MAX_CODE_WIDTH`])
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::InvalidCodeWidth`] - nieobsługiwana ilość cyfr
+    /// * [`Error::KeyTooShort`] - mniej pozycji kodów niż symboli
+    /// * [`Error::DuplicateSymbol`] - symbol tekstu jawnego powtarza się
+    /// * [`Error::CodeOutOfRange`] - kod ma więcej cyfr niż `width`
+    /// * [`Error::DuplicateCode`] - kod przypisany jest więcej niż raz
+    pub fn new(labels: Vec<char>, codes: Vec<Vec<u32>>, width: usize) -> Result<NumericKey, Error> {
+        if width == 0 || width > MAX_CODE_WIDTH {
+            return Err(Error::InvalidCodeWidth { width });
+        }
+        if codes.len() < labels.len() {
+            return Err(Error::KeyTooShort { expected: labels.len(), found: codes.len() });
+        }
+        let mut seen: HashSet<char> = HashSet::new();
+        if let Some(&character) = labels.iter().find(|&&c: &&char| !seen.insert(c)) {
+            return Err(Error::DuplicateSymbol { character });
+        }
+        let limit: u32 = 10u32.pow(width as u32);
+        let mut used: HashSet<u32> = HashSet::new();
+        for &code in codes.iter().flatten() {
+            if code >= limit {
+                return Err(Error::CodeOutOfRange { code, width });
+            }
+            if !used.insert(code) {
+                return Err(Error::DuplicateCode { code });
+            }
+        }
+        Ok(NumericKey { labels, codes, width })
+    }
+
+    /// Zczytuje klucz z pliku
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyIo`] - błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
+    /// * błędy formatu (patrz [`NumericKey::parse`])
+    pub fn read(path: &str) -> Result<NumericKey, Error> {
+        let str: String = fs::read_to_string(path)
+            .map_err(|source: io::Error| Error::KeyIo { path: path.to_string(), source })?;
+        NumericKey::parse(&str)
+    }
+
+    /// Zczytuje klucz z pliku wyszukiwanego w katalogach kluczy (patrz [`crate::utils::search_path`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`NumericKey::read`]
+    pub fn read_translation(path: &str) -> Result<NumericKey, Error> {
+        NumericKey::read(&translation_path(path))
+    }
+
+    /// Symbole tekstu jawnego
+    pub fn labels(&self) -> &[char] {
+        &self.labels
+    }
+
+    /// Kody kolejnych symboli
+    pub fn codes(&self) -> &[Vec<u32>] {
+        &self.codes
+    }
+
+    /// Ilość cyfr każdego kodu
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Tworzy klucz z tekstu w formacie pliku klucza z kodami liczbowymi
+    /// 
+    /// # Arguments
+    /// 
+    /// * `str` - zawartość klucza
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::MalformedKeyLine`] - linijka nie ma postaci `symbol: kody` bądź kody mają różną ilość cyfr
+    /// * błędy tworzenia klucza (patrz [`NumericKey::new`])
+    pub fn parse(str: &str) -> Result<NumericKey, Error> {
+        let mut labels: Vec<char> = Vec::new();
+        let mut codes: Vec<Vec<u32>> = Vec::new();
+        let mut width: Option<usize> = None;
+        for (index, line) in str.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut chars: std::str::Chars = line.trim_start().chars();
+            let label: char = match (chars.next(), chars.next()) {
+                (Some(label), Some(':')) => label,
+                _ => return Err(Error::MalformedKeyLine { line: index + 1 })
+            };
+            let mut position: Vec<u32> = Vec::new();
+            for code in chars.as_str().split_whitespace() {
+                if !code.chars().all(|c: char| c.is_ascii_digit()) || *width.get_or_insert(code.len()) != code.len() {
+                    return Err(Error::MalformedKeyLine { line: index + 1 });
+                }
+                position.push(code.parse().map_err(|_| Error::MalformedKeyLine { line: index + 1 })?);
+            }
+            labels.push(label);
+            codes.push(position);
+        }
+        NumericKey::new(labels, codes, width.unwrap_or(2))
+    }
+
+    /// Zapisuje klucz do pliku
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka zapisu
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyIo`] - błędy związane z zapisem pliku (np. brak uprawnień)
+    pub fn write(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.to_string())
+            .map_err(|source: io::Error| Error::KeyIo { path: path.to_string(), source })
+    }
+}
+
+/// Zapis w formacie pliku klucza (kody uzupełniane zerami do pełnej ilości cyfr)
+impl fmt::Display for NumericKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, codes) in self.labels.iter().zip(&self.codes) {
+            let codes: Vec<String> = codes.iter().map(|code: &u32| format!("{:0width$}", code, width = self.width)).collect();
+            writeln!(f, "{}: {}", label, codes.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parametry generowania klucza z kodami liczbowymi
+#[derive(Debug, Clone)]
+pub struct NumericKeyParams {
+    /// Alfabet tekstu jawnego (każdy symbol otrzymuje co najmniej jeden kod)
+    pub alphabet: Alphabet,
+    /// Wagi kolejnych symboli alfabetu (np. [`Language::frequencies`]; pusty wektor - równe wagi)
+    pub frequencies: Vec<f64>,
+    /// Ilość cyfr każdego kodu (rozdzielane są wszystkie kody od 0 do 10^width - 1)
+    pub width: usize
+}
+
+/// Metody parametrów generowania klucza z kodami liczbowymi
+impl NumericKeyParams {
+    /// Parametry dla liter podanego języka z ilością kodów proporcjonalną do częstości liter
+    /// 
+    /// # Arguments
+    /// 
+    /// * `language` - język tekstu jawnego
+    /// * `width` - ilość cyfr każdego kodu
+    pub fn for_language(language: Language, width: usize) -> NumericKeyParams {
+        NumericKeyParams { alphabet: language.alphabet(), frequencies: language.frequencies().to_vec(), width }
+    }
+}
+
+/// Domyślne parametry: litery A-Z z częstością języka angielskiego, kody dwucyfrowe (00-99)
+impl Default for NumericKeyParams {
+    fn default() -> NumericKeyParams {
+        NumericKeyParams::for_language(Language::English, 2)
+    }
+}
+
+/// Do określania w jaki sposób zapisywać kody w szyfrogramie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumericFormat {
+    /// Kody rozdzielone spacjami, spacja tekstu jawnego zapisywana jest jako pusty kod (podwójna spacja)
+    #[default]
+    Spaced,
+    /// Kody zapisane bez separatorów, białe znaki przepisywane bez zmian
+    Fixed
+}
+
+/// Przechowuje dodatkowe infromacje potrzebne dla szyfru homofonicznego z kodami liczbowymi
+/// 
+/// Symbole tekstu jawnego dopasowywane są bez względu na wielkość liter
+pub struct NumericHomophonicData {
+    /// Szyfr homofoniczny, w którym kody reprezentowane są znakami z obszaru prywatnego Unicode
+    homophonic: HomophonicData,
+    /// Ilość cyfr każdego kodu
+    width: usize,
+    /// Sposób zapisu kodów w szyfrogramie
+    format: NumericFormat
+}
+
+/// Metody dodatkowe związane z szyfrem homofonicznym z kodami liczbowymi
+impl NumericHomophonicData {
+    /// Tworzy nową strukturę z informacjami szyfru homofonicznego z kodami liczbowymi
+    /// 
+    /// # Arguments
+    /// 
+    /// * `key` - klucz z kodami liczbowymi
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::EmptyAlphabet`] - klucz nie posiada żadnego symbolu
+    pub fn new(key: NumericKey) -> Result<NumericHomophonicData, Error> {
+        let alphabet: Alphabet = Alphabet::new(key.labels)?;
+        let data: Vec<String> = key.codes.iter()
+                                         .map(|codes: &Vec<u32>| codes.iter().map(|&code: &u32| to_symbol(code)).collect())
+                                         .collect();
+        let homophonic: HomophonicData = HomophonicData::with_alphabet(Key::new(data, KeyRule::Row), alphabet)?;
+        Ok(NumericHomophonicData { homophonic, width: key.width, format: NumericFormat::default() })
+    }
+
+    /// Tworzy losowy klucz, w którym wszystkie kody o podanej ilości cyfr rozdzielone są pomiędzy
+    /// symbole alfabetu proporcjonalnie do ich wag (każdy symbol otrzymuje co najmniej jeden kod)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - parametry generowania klucza
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::InvalidCodeWidth`] - nieobsługiwana ilość cyfr
+    /// * [`Error::InsufficientSymbols`] - kodów jest mniej niż symboli alfabetu
+    pub fn generate_key(params: &NumericKeyParams) -> Result<NumericKey, Error> {
+        NumericHomophonicData::generate_key_with_rng(params, &mut rand::thread_rng())
+    }
+
+    /// Tworzy losowy klucz z kodami liczbowymi korzystając z podanego generatora liczb losowych
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - parametry generowania klucza
+    /// * `rng` - generator liczb losowych
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`NumericHomophonicData::generate_key`]
+    pub fn generate_key_with_rng(params: &NumericKeyParams, rng: &mut impl Rng) -> Result<NumericKey, Error> {
+        if params.width == 0 || params.width > MAX_CODE_WIDTH {
+            return Err(Error::InvalidCodeWidth { width: params.width });
+        }
+        let total: usize = 10usize.pow(params.width as u32);
+        let weights: Vec<f64> = match params.frequencies.len() == params.alphabet.len() {
+            true => params.frequencies.clone(),
+            false => vec![1.0; params.alphabet.len()]
+        };
+        let counts: Vec<usize> = allocate(&weights, total)
+            .ok_or(Error::InsufficientSymbols { required: weights.len(), found: total })?;
+
+        let mut pool: Vec<u32> = (0..total as u32).collect();
+        pool.shuffle(rng);
+        let mut pool: std::vec::Drain<u32> = pool.drain(..);
+        let codes: Vec<Vec<u32>> = counts.iter()
+                                         .map(|&count: &usize| {
+                                             let mut codes: Vec<u32> = pool.by_ref().take(count).collect();
+                                             codes.sort_unstable();
+                                             codes
+                                         })
+                                         .collect();
+        NumericKey::new(params.alphabet.symbols().to_vec(), codes, params.width)
+    }
+
+    /// Ustawia sposób zapisu kodów w szyfrogramie
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - sposób zapisu kodów
+    pub fn format(mut self, format: NumericFormat) -> NumericHomophonicData {
+        self.format = format;
+        self
+    }
+
+    /// Ustawia generator liczb losowych wykorzystywany przy wyborze kodów
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rng` - generator liczb losowych
+    pub fn rng(mut self, rng: impl RngCore + 'static) -> NumericHomophonicData {
+        self.homophonic = self.homophonic.rng(rng);
+        self
+    }
+
+    /// Ustawia sposób wyboru kodów (patrz [`HomophoneStrategy`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strategy` - sposób wyboru kodów
+    pub fn strategy(mut self, strategy: HomophoneStrategy) -> NumericHomophonicData {
+        self.homophonic = self.homophonic.strategy(strategy);
+        self
+    }
+
+    /// Ustawia sposób obsługi znaków tekstu jawnego, które nie mają kodów (domyślnie przepisywane bez zmian;
+    /// cyfry ASCII nigdy nie są przepisywane, gdyż zostałyby odczytane jako kody)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `unmapped` - sposób obsługi znaków bez kodów
+    pub fn unmapped(mut self, unmapped: Unknown) -> NumericHomophonicData {
+        self.homophonic = self.homophonic.unmapped(unmapped);
+        self
+    }
+
+    /// Ustawia sposób obsługi znaków i kodów szyfrogramu, których nie ma w kluczu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `unknown` - sposób obsługi nieznanych znaków
+    pub fn unknown(mut self, unknown: Unknown) -> NumericHomophonicData {
+        self.homophonic = self.homophonic.unknown(unknown);
+        self
+    }
+
+    /// Szyfruje ciąg znaków, którego pierwszy znak znajduje się na podanej pozycji tekstu,
+    /// obsługując znaki bez kodów zgodnie z [`HomophonicData::unmapped_sign`] (`line_started` - czy bieżąca linijka
+    /// szyfrogramu zawiera już kody, `lenient` - czy zamiast zgłaszać błąd należy zastąpić znak)
+    fn encode(&self, data: &str, start: usize, line_started: &mut bool, lenient: bool) -> Result<String, Error> {
+        let mut output: String = String::with_capacity(data.len() * (self.width + 1));
+        for (position, sign) in data.chars().enumerate() {
+            if self.format == NumericFormat::Spaced {
+                if sign == '\n' {
+                    output.push(sign);
+                    *line_started = false;
+                    continue;
+                }
+                if *line_started {
+                    output.push(' ');
+                }
+                *line_started = true;
+                if sign == ' ' {
+                    continue;
+                }
+            }
+            if sign.is_whitespace() {
+                output.push(sign);
+                continue;
+            }
+            match self.homophonic.substitute(sign, start + position) {
+                Some(symbol) => output.push_str(&format!("{:0width$}", from_symbol(symbol), width = self.width)),
+                None => {
+                    let copied: char = self.homophonic.unmapped_sign(sign, position, lenient)?;
+                    // przepisana cyfra zostałaby odczytana jako (część) kodu
+                    match copied.is_ascii_digit() {
+                        true if lenient => output.push(char::REPLACEMENT_CHARACTER),
+                        true => return Err(Error::UnknownSymbol { character: sign, position }),
+                        false => output.push(copied)
+                    }
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    /// Odszyfrowuje ciąg znaków obsługując nieznane znaki i kody w podany sposób
+    /// (`line_started` - czy bieżąca linijka szyfrogramu zawiera już kody z poprzednich fragmentów)
+    fn decode(&self, data: &str, line_started: &mut bool, unknown: Unknown) -> Result<String, Error> {
+        let chars: Vec<char> = data.chars().collect();
+        let mut output: String = String::with_capacity(data.len() / self.width + 1);
+        if self.format == NumericFormat::Fixed {
+            self.decode_run(&chars, 0, unknown, &mut output)?;
+            return Ok(output);
+        }
+        // w każdej niepustej linijce elementy rozdzielone są pojedynczą spacją, pusty element oznacza spację
+        let mut position: usize = 0;
+        for (index, line) in chars.split(|&c: &char| c == '\n').enumerate() {
+            if index > 0 {
+                output.push('\n');
+                position += 1;
+                *line_started = false;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            // fragment kontynuujący linijkę zaczyna się od separatora po elemencie z poprzedniego fragmentu
+            let continued: bool = *line_started && line[0] == ' ';
+            *line_started = true;
+            for (number, token) in line.split(|&c: &char| c == ' ').enumerate() {
+                match token.is_empty() {
+                    _ if continued && number == 0 => {},
+                    true => output.push(' '),
+                    false => self.decode_run(token, position, unknown, &mut output)?
+                }
+                position += token.len() + 1;
+            }
+            position -= 1;
+        }
+        Ok(output)
+    }
+
+    /// Odszyfrowuje ciąg kodów zapisanych bez separatorów, którego pierwszy znak znajduje się na podanej pozycji
+    /// (nieznany kod zgłaszany jest jako [`Error::UnknownCode`], przepisywany bądź zastępowany w całości)
+    fn decode_run(&self, chars: &[char], start: usize, unknown: Unknown, output: &mut String) -> Result<(), Error> {
+        let mut position: usize = 0;
+        while position < chars.len() {
+            let sign: char = chars[position];
+            let group: &[char] = &chars[position..chars.len().min(position + self.width)];
+            if group.len() == self.width && group.iter().all(char::is_ascii_digit) {
+                let code: u32 = group.iter().collect::<String>().parse().expect("Checked above");
+                // nieznany kod obsługiwany jest w całości
+                match (self.homophonic.plaintext(to_symbol(code)), unknown) {
+                    (Some(symbol), _) => output.push(symbol),
+                    (None, Unknown::Reject) => return Err(Error::UnknownCode { code: group.iter().collect(), position: start + position }),
+                    (None, Unknown::Keep) => output.extend(group),
+                    (None, Unknown::Replace(placeholder)) => output.push(placeholder)
+                }
+                position += self.width;
+                continue;
+            }
+            match sign.is_whitespace() {
+                true => output.push(sign),
+                false => output.push(unknown.handle(sign, start + position)?)
+            }
+            position += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Implementacja metod szyfrujących
+impl Cipher for NumericHomophonicData {
+    fn encrypt(&self, data: &str) -> String {
+        self.encode(data, 0, &mut false, true).unwrap_or_default()
+    }
+    fn try_encrypt(&self, data: &str) -> Result<String, Error> {
+        self.encode(data, 0, &mut false, false)
+    }
+    fn encrypt_at(&self, data: &str, position: &mut Position) -> String {
+        let output: String = self.encode(data, position.characters, &mut position.line_started, true)
+                                 .unwrap_or_default();
+        position.characters += data.chars().count();
+        output
+    }
+    fn try_encrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        let output: String = self.encode(data, position.characters, &mut position.line_started, false)
+                                 .map_err(|error: Error| error.shifted(position.characters))?;
+        position.characters += data.chars().count();
+        Ok(output)
+    }
+    fn decrypt(&self, data: &str) -> String {
+        self.decode(data, &mut false, self.homophonic.policies().1.lenient()).unwrap_or_default()
+    }
+    fn try_decrypt(&self, data: &str) -> Result<String, Error> {
+        self.decode(data, &mut false, self.homophonic.policies().1)
+    }
+    fn try_decrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        let output: String = self.decode(data, &mut position.line_started, self.homophonic.policies().1)
+                                 .map_err(|error: Error| error.shifted(position.characters))?;
+        position.characters += data.chars().count();
+        Ok(output)
+    }
+}
+
+/// Zamienia kod liczbowy na znak z obszaru prywatnego Unicode
+fn to_symbol(code: u32) -> char {
+    char::from_u32(CODE_BASE + code).expect("Codes have at most MAX_CODE_WIDTH digits")
+}
+
+/// Zamienia znak z obszaru prywatnego Unicode na kod liczbowy
+fn from_symbol(symbol: char) -> u32 {
+    symbol as u32 - CODE_BASE
+}
@@ -1,71 +1,286 @@
-use std::collections::HashMap;
-use crate::algorithms::Cipher;
-use crate::utils::Key;
+use crate::algorithms::{Cipher, Position};
+use crate::utils::{Key, KeyRule};
+use crate::{Alphabet, Error};
+use rand::{Rng, prelude::SliceRandom};
 
 /// Przechowuje dodatkowe infromacje potrzebne dla szyfru wieloalfabetowego (Szyfr Vigenère'a)
-pub struct PolyalphabeticData(Key);
+pub struct PolyalphabeticData {
+    /// Klucz szyfrujący
+    code: Key,
+    /// Alfabet, w którym przesuwane są znaki
+    alphabet: Alphabet,
+    /// Sposób przechodzenia do kolejnej pozycji klucza
+    advance: KeyAdvance,
+    /// Tablice podstawień dla kolejnych pozycji klucza (wyznaczane raz przy tworzeniu szyfru)
+    substitutions: Substitutions
+}
+
+/// Parametry generowania klucza szyfru wieloalfabetowego
+#[derive(Debug, Clone)]
+pub struct PolyalphabeticKeyParams {
+    /// Alfabet szyfru
+    pub alphabet: Alphabet,
+    /// Ilość pozycji klucza (okres szyfru)
+    pub length: usize,
+    /// Czy każda pozycja ma być pełnym alfabetem podstawieniowym (patrz [`PolyalphabeticData::substitution`])
+    pub substitution: bool
+}
+
+/// Domyślne parametry: alfabet łaciński (A-Z + a-z), 8 pozycji przesunięć
+impl Default for PolyalphabeticKeyParams {
+    fn default() -> PolyalphabeticKeyParams {
+        PolyalphabeticKeyParams { alphabet: Alphabet::latin_mixed(), length: 8, substitution: false }
+    }
+}
+
+/// Alfabety podstawieniowe kolejnych pozycji klucza zapisane jako indeksy symboli
+struct Substitutions {
+    /// Indeks szyfrogramu dla każdego indeksu tekstu jawnego
+    forward: Vec<Vec<usize>>,
+    /// Indeks tekstu jawnego dla każdego indeksu szyfrogramu
+    inverse: Vec<Vec<usize>>
+}
+
+/// Do określania kiedy przechodzić do kolejnej pozycji klucza
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyAdvance {
+    /// Po każdym znaku tekstu (również spacjach i znakach interpunkcyjnych)
+    #[default]
+    EveryCharacter,
+    /// Tylko po literach (klasyczny szyfr Vigenère'a)
+    Alphabetic,
+    /// Tylko po symbolach należących do alfabetu szyfru
+    AlphabetSymbol
+}
 
 /// Metody dodatkowe związane z szyfrem wieloalfabetowym (Szyfr Vigenère'a)
 impl PolyalphabeticData {
     /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego (Szyfr Vigenère'a)
+    /// dla alfabetu z nagłówka klucza (domyślnie alfabet łaciński A-Z + a-z)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::NonAlphabeticKey`] - klucz musi posiadać wyłącznie znaki z alfabetu
+    pub fn new(code: Key) -> Result<PolyalphabeticData, Error> {
+        let alphabet: Alphabet = code.alphabet().cloned().unwrap_or_else(Alphabet::latin_mixed);
+        PolyalphabeticData::with_alphabet(code, alphabet)
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego (Szyfr Vigenère'a) dla podanego alfabetu
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania
+    /// * `alphabet` - alfabet, w którym przesuwane są znaki
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyTooShort`] - klucz musi posiadać co najmniej jedną pozycję
+    /// * [`Error::NonAlphabeticKey`] - klucz musi posiadać wyłącznie znaki z alfabetu
+    pub fn with_alphabet(code: Key, alphabet: Alphabet) -> Result<PolyalphabeticData, Error> {
+        if code.data.is_empty() {
+            return Err(Error::KeyTooShort { expected: 1, found: 0 })
+        }
+        for (line, vec) in code.data.iter().enumerate() {
+            if let Some(character) = vec.chars().find(|&c: &char| alphabet.find(c).is_none()) {
+                return Err(Error::NonAlphabeticKey { character, line: line + 1 })
+            }
+        }
+        let forward: Vec<Vec<usize>> = code.data.iter()
+            .map(|vec: &String| {
+                let offset: usize = vec.chars()
+                                       .next()
+                                       .and_then(|c: char| alphabet.find(c))
+                                       .unwrap_or(0);
+                (0..alphabet.len()).map(|index: usize| (index + offset) % alphabet.len()).collect()
+            })
+            .collect();
+        let substitutions: Substitutions = Substitutions::new(forward);
+        Ok(PolyalphabeticData { code, alphabet, advance: KeyAdvance::default(), substitutions })
+    }
+
+    /// Tworzy losowy klucz, który zawsze jest poprawnym kluczem szyfru wieloalfabetowego dla podanego alfabetu
+    /// (pojedyncze symbole alfabetu bądź, dla `substitution`, permutacje całego alfabetu)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - parametry generowania klucza
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyTooShort`] - klucz musi posiadać co najmniej jedną pozycję
+    pub fn generate_key(params: &PolyalphabeticKeyParams) -> Result<Key, Error> {
+        PolyalphabeticData::generate_key_with_rng(params, &mut rand::thread_rng())
+    }
+
+    /// Tworzy losowy klucz szyfru wieloalfabetowego korzystając z podanego generatora liczb losowych
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - parametry generowania klucza
+    /// * `rng` - generator liczb losowych
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`PolyalphabeticData::generate_key`]
+    pub fn generate_key_with_rng(params: &PolyalphabeticKeyParams, rng: &mut impl Rng) -> Result<Key, Error> {
+        if params.length == 0 {
+            return Err(Error::KeyTooShort { expected: 1, found: 0 });
+        }
+        let symbols: &[char] = params.alphabet.symbols();
+        let data: Vec<String> = (0..params.length)
+            .map(|_| match params.substitution {
+                true => {
+                    let mut row: Vec<char> = symbols.to_vec();
+                    row.shuffle(rng);
+                    row.into_iter().collect()
+                },
+                false => symbols[rng.gen_range(0..symbols.len())].to_string()
+            })
+            .collect();
+        Ok(Key::new(data, KeyRule::Row))
+    }
+
+    /// Tworzy nową strukturę z informacjami ogólnego okresowego szyfru wieloalfabetowego,
+    /// w którym każda pozycja klucza jest pełnym alfabetem podstawieniowym
+    /// (kolejne symbole pozycji zastępują kolejne symbole alfabetu)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - klucz do wykorzystania (każda pozycja musi być permutacją alfabetu)
+    /// * `alphabet` - alfabet tekstu jawnego
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyTooShort`] - klucz musi posiadać co najmniej jedną pozycję
+    /// * [`Error::NonAlphabeticKey`] - klucz musi posiadać wyłącznie znaki z alfabetu
+    /// * [`Error::NotPermutation`] - każda pozycja klucza musi zawierać każdy symbol alfabetu dokładnie raz
+    pub fn substitution(code: Key, alphabet: Alphabet) -> Result<PolyalphabeticData, Error> {
+        let mut data: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, alphabet)?;
+        let mut forward: Vec<Vec<usize>> = Vec::with_capacity(data.code.data.len());
+        for (line, vec) in data.code.data.iter().enumerate() {
+            let row: Vec<usize> = vec.chars()
+                                     .filter_map(|c: char| data.alphabet.find(c))
+                                     .collect();
+            let mut positions: Vec<Option<usize>> = vec![None; data.alphabet.len()];
+            for (position, &index) in row.iter().enumerate() {
+                if positions[index].replace(position).is_some() {
+                    return Err(Error::NotPermutation { character: data.alphabet.symbol(index), line: line + 1 })
+                }
+            }
+            if let Some(index) = positions.iter().position(Option::is_none) {
+                return Err(Error::NotPermutation { character: data.alphabet.symbol(index), line: line + 1 })
+            }
+            forward.push(row);
+        }
+        data.substitutions = Substitutions::new(forward);
+        Ok(data)
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego (Szyfr Vigenère'a)
+    /// ze słowa kluczowego dla alfabetu łacińskiego (A-Z, z zachowaniem wielkości liter)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `keyword` - słowo kluczowe (np. `"LEMON"`)
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`PolyalphabeticData::with_alphabet`]
+    pub fn from_keyword(keyword: &str) -> Result<PolyalphabeticData, Error> {
+        PolyalphabeticData::from_keyword_with_alphabet(keyword, Alphabet::latin_upper())
+    }
+
+    /// Tworzy nową strukturę z informacjami szyfru wieloalfabetowego (Szyfr Vigenère'a)
+    /// ze słowa kluczowego dla podanego alfabetu
     /// 
     /// # Arguments
     /// 
-    /// * `code` - klucz do wykorzystania (musi spełniać warunki[^1])
+    /// * `keyword` - słowo kluczowe
+    /// * `alphabet` - alfabet, w którym przesuwane są znaki
     /// 
     /// # Errors
     /// 
-    /// * [^1]Klucz musi posiadać znaki alfabetyczne ascii (a-z + A-Z)
-    pub fn new(code: Key) -> Result<PolyalphabeticData, Box<dyn std::error::Error>> {
-        if code.data.iter().any(|s: &String| s.chars().any(|c: char| !c.is_alphabetic())) {
-            return Err("Invalid code! Valid code must contain ASCII alphabetic characters".into())
+    /// Patrz [`PolyalphabeticData::with_alphabet`]
+    pub fn from_keyword_with_alphabet(keyword: &str, alphabet: Alphabet) -> Result<PolyalphabeticData, Error> {
+        PolyalphabeticData::with_alphabet(Key::from_keyword(keyword), alphabet)
+    }
+
+    /// Ustawia sposób przechodzenia do kolejnej pozycji klucza
+    /// 
+    /// # Arguments
+    /// 
+    /// * `advance` - kiedy przechodzić do kolejnej pozycji klucza
+    pub fn advance(mut self, advance: KeyAdvance) -> PolyalphabeticData {
+        self.advance = advance;
+        self
+    }
+
+    /// Czy po podanym znaku należy przejść do kolejnej pozycji klucza
+    fn advances(&self, sign: char) -> bool {
+        match self.advance {
+            KeyAdvance::EveryCharacter => true,
+            KeyAdvance::Alphabetic => sign.is_alphabetic(),
+            KeyAdvance::AlphabetSymbol => self.alphabet.find(sign).is_some()
         }
-        Ok(PolyalphabeticData(code))
+    }
+
+    /// Szyfruje bądź odszyfrowuje ciąg znaków zaczynając od podanej pozycji klucza
+    fn process(&self, data: &str, step: &mut usize, decrypt: bool) -> String {
+        let tables: &[Vec<usize>] = match decrypt {
+            true => &self.substitutions.inverse,
+            false => &self.substitutions.forward
+        };
+        data.chars().map(|sign: char| {
+            let table: &[usize] = &tables[*step % tables.len()];
+            if self.advances(sign) {
+                *step += 1;
+            }
+            self.alphabet.translate(sign, |index: usize| table[index])
+                         .unwrap_or(sign)
+        }).collect::<String>()
+    }
+}
+
+/// Metody tablic podstawień
+impl Substitutions {
+    /// Tworzy tablice podstawień wyznaczając tablice odwrotne
+    /// 
+    /// # Arguments
+    /// 
+    /// * `forward` - permutacje indeksów alfabetu dla kolejnych pozycji klucza
+    fn new(forward: Vec<Vec<usize>>) -> Substitutions {
+        let inverse: Vec<Vec<usize>> = forward.iter()
+            .map(|row: &Vec<usize>| {
+                let mut inverse: Vec<usize> = vec![0; row.len()];
+                row.iter()
+                   .enumerate()
+                   .for_each(|(index, &substitute): (usize, &usize)| inverse[substitute] = index);
+                inverse
+            })
+            .collect();
+        Substitutions { forward, inverse }
     }
 }
 
 /// Implementacja metod szyfrujących
 impl Cipher for PolyalphabeticData {
     fn encrypt(&self, data: &str) -> String { 
-        let ascii: HashMap<char, usize> = ('A'..='Z').chain('a'..='z')
-                                                    .enumerate()
-                                                    .map(|(index, sign): (usize, char)| (sign, index))
-                                                    .collect();
-
-        data.chars().zip(self.0.data.iter().cycle()).map(|(sign, vec): (char, &String)|{
-            if sign.is_ascii_alphabetic() {
-                let code_char: char = vec.chars().nth(0).unwrap();
-
-                let data_num: usize = ascii[&sign];
-                let code_num: usize = ascii[&code_char];
-                let result: usize = (data_num + code_num) % ascii.len();
-                *ascii.iter().find(|(_k, &v): &(&char, &usize)| v == result).unwrap().0
-            } else {
-                sign
-            }
-        }).collect::<String>() 
+        self.process(data, &mut 0, false)
     }
     fn decrypt(&self, data: &str) -> String { 
-        let ascii: HashMap<char, usize> = ('A'..='Z').chain('a'..='z')
-                                                    .enumerate()
-                                                    .map(|(index, sign): (usize, char)| (sign, index))
-                                                    .collect();
-
-        data.chars().zip(self.0.data.iter().cycle()).map(|(sign, vec): (char, &String)|{
-            if sign.is_ascii_alphabetic() {
-                let code_char: char = vec.chars().nth(0).unwrap();
-
-                let data_num: i8 = ascii[&sign] as i8;
-                let code_num: i8 = ascii[&code_char] as i8;
-                let mut result: i8 = data_num - code_num;
-                if result < 0 { 
-                    result = ascii.len() as i8 + result; 
-                }
-                let result = result as usize % ascii.len();
-                *ascii.iter().find(|(_k, &v): &(&char, &usize)| v == result).unwrap().0
-            } else {
-                sign
-            }
-        }).collect::<String>() 
+        self.process(data, &mut 0, true)
+    }
+    fn encrypt_at(&self, data: &str, position: &mut Position) -> String {
+        position.characters += data.chars().count();
+        self.process(data, &mut position.key, false)
     }
-}
\ No newline at end of file
+    fn try_decrypt_at(&self, data: &str, position: &mut Position) -> Result<String, Error> {
+        position.characters += data.chars().count();
+        Ok(self.process(data, &mut position.key, true))
+    }
+}
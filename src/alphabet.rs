@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+use std::{fs, fmt};
+use crate::Error;
+use crate::utils::translation_path;
+
+/// Uporządkowany zbiór symboli, na którym operują szyfry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    /// Symbole w kolejności alfabetycznej
+    symbols: Vec<char>,
+    /// Indeksy symboli
+    indices: HashMap<char, usize>,
+    /// Indeksy symboli ASCII (bez przeszukiwania mapy), `usize::MAX` oznacza brak symbolu
+    ascii: [usize; 128]
+}
+
+/// Metody alfabetu
+impl Alphabet {
+    /// Tworzy alfabet z podanych symboli
+    /// 
+    /// # Arguments
+    /// 
+    /// * `symbols` - symbole alfabetu w kolejności
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::EmptyAlphabet`] - alfabet musi zawierać co najmniej jeden symbol
+    /// * [`Error::DuplicateSymbol`] - symbole alfabetu nie mogą się powtarzać
+    pub fn new(symbols: Vec<char>) -> Result<Alphabet, Error> {
+        if symbols.is_empty() {
+            return Err(Error::EmptyAlphabet);
+        }
+        let mut indices: HashMap<char, usize> = HashMap::with_capacity(symbols.len());
+        let mut ascii: [usize; 128] = [usize::MAX; 128];
+        for (index, &character) in symbols.iter().enumerate() {
+            if indices.insert(character, index).is_some() {
+                return Err(Error::DuplicateSymbol { character });
+            }
+            if character.is_ascii() {
+                ascii[character as usize] = index;
+            }
+        }
+        Ok(Alphabet { symbols, indices, ascii })
+    }
+
+    /// Wielkie litery alfabetu łacińskiego (A-Z)
+    pub fn latin_upper() -> Alphabet {
+        Alphabet::from_preset(('A'..='Z').collect())
+    }
+
+    /// Wielkie i małe litery alfabetu łacińskiego (A-Z, a-z)
+    pub fn latin_mixed() -> Alphabet {
+        Alphabet::from_preset(('A'..='Z').chain('a'..='z').collect())
+    }
+
+    /// Wielkie litery alfabetu polskiego (32 litery)
+    pub fn polish() -> Alphabet {
+        Alphabet::from_preset("AĄBCĆDEĘFGHIJKLŁMNŃOÓPRSŚTUWYZŹŻ".chars().collect())
+    }
+
+    /// Wielkie i małe litery alfabetu polskiego (64 litery)
+    pub fn polish_mixed() -> Alphabet {
+        Alphabet::from_preset("AĄBCĆDEĘFGHIJKLŁMNŃOÓPRSŚTUWYZŹŻaąbcćdeęfghijklłmnńoóprsśtuwyzźż".chars().collect())
+    }
+
+    /// Cyfry dziesiętne (0-9)
+    pub fn digits() -> Alphabet {
+        Alphabet::from_preset(('0'..='9').collect())
+    }
+
+    /// Drukowalne znaki ASCII (bez spacji)
+    pub fn printable_ascii() -> Alphabet {
+        Alphabet::from_preset(('!'..='~').collect())
+    }
+
+    /// Zwraca wbudowany alfabet o podanej nazwie
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - nazwa alfabetu (`latin-upper`, `latin-mixed`, `polish`, `polish-mixed`, `digits`, `ascii`)
+    pub fn preset(name: &str) -> Option<Alphabet> {
+        match name {
+            "latin-upper" | "latin" => Some(Alphabet::latin_upper()),
+            "latin-mixed" => Some(Alphabet::latin_mixed()),
+            "polish" => Some(Alphabet::polish()),
+            "polish-mixed" => Some(Alphabet::polish_mixed()),
+            "digits" => Some(Alphabet::digits()),
+            "ascii" => Some(Alphabet::printable_ascii()),
+            _ => None
+        }
+    }
+
+    /// Zwraca nazwę wbudowanego alfabetu równego temu alfabetowi (`None` dla pozostałych alfabetów)
+    pub fn name(&self) -> Option<&'static str> {
+        ["latin-upper", "latin-mixed", "polish", "polish-mixed", "digits", "ascii"]
+            .into_iter()
+            .find(|&name: &&str| Alphabet::preset(name).as_ref() == Some(self))
+    }
+
+    fn from_preset(symbols: Vec<char>) -> Alphabet {
+        Alphabet::new(symbols).expect("Built-in alphabets contain unique symbols")
+    }
+
+    /// Zczytuje alfabet z pliku (wszystkie znaki poza białymi w kolejności wystąpienia)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::KeyIo`] - błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
+    /// * błędy tworzenia alfabetu (patrz [`Alphabet::new`])
+    pub fn read(path: &str) -> Result<Alphabet, Error> {
+        let str: String = fs::read_to_string(path)
+            .map_err(|source: std::io::Error| Error::KeyIo { path: path.to_string(), source })?;
+        Alphabet::new(str.chars().filter(|c: &char| !c.is_whitespace()).collect())
+    }
+
+    /// Zczytuje alfabet z pliku wyszukiwanego w katalogach kluczy (patrz [`crate::utils::search_path`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - ścieżka odczytu
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`Alphabet::read`]
+    pub fn read_translation(path: &str) -> Result<Alphabet, Error> {
+        Alphabet::read(&translation_path(path))
+    }
+
+    /// Ilość symboli w alfabecie
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Czy alfabet jest pusty (zawsze fałsz dla poprawnie utworzonego alfabetu)
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Symbole alfabetu w kolejności
+    pub fn symbols(&self) -> &[char] {
+        &self.symbols
+    }
+
+    /// Zwraca symbol o podanym indeksie (indeks brany jest modulo długość alfabetu)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `index` - indeks symbolu
+    pub fn symbol(&self, index: usize) -> char {
+        self.symbols[index % self.symbols.len()]
+    }
+
+    /// Zwraca indeks symbolu (bez uwzględniania innej wielkości liter)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `character` - szukany znak
+    pub fn index(&self, character: char) -> Option<usize> {
+        if character.is_ascii() {
+            return Some(self.ascii[character as usize]).filter(|&index: &usize| index != usize::MAX);
+        }
+        self.indices.get(&character).copied()
+    }
+
+    /// Zwraca indeks symbolu; jeśli znaku nie ma w alfabecie sprawdzana jest jego druga wielkość liter
+    /// 
+    /// # Arguments
+    /// 
+    /// * `character` - szukany znak
+    pub fn find(&self, character: char) -> Option<usize> {
+        self.index(character)
+            .or_else(|| swap_case(character).and_then(|c: char| self.index(c)))
+    }
+
+    /// Zamienia znak na symbol alfabetu o indeksie wyznaczonym przez podaną funkcję,
+    /// zachowując wielkość liter jeśli znak został odnaleziony po zmianie wielkości
+    /// 
+    /// # Arguments
+    /// 
+    /// * `character` - znak do zamiany
+    /// * `map` - funkcja wyznaczająca nowy indeks (brany modulo długość alfabetu)
+    pub fn translate(&self, character: char, map: impl FnOnce(usize) -> usize) -> Option<char> {
+        if let Some(index) = self.index(character) {
+            return Some(self.symbol(map(index)));
+        }
+        let index: usize = self.index(swap_case(character)?)?;
+        let symbol: char = self.symbol(map(index));
+        Some(swap_case(symbol).unwrap_or(symbol))
+    }
+}
+
+/// Zmienia wielkość litery (o ile wynikiem jest dokładnie jeden znak)
+fn swap_case(character: char) -> Option<char> {
+    if character.is_ascii() {
+        return match character {
+            'a'..='z' => Some(character.to_ascii_uppercase()),
+            'A'..='Z' => Some(character.to_ascii_lowercase()),
+            _ => None
+        };
+    }
+    if character.is_uppercase() {
+        single(character.to_lowercase())
+    } else if character.is_lowercase() {
+        single(character.to_uppercase())
+    } else {
+        None
+    }
+}
+
+/// Zwraca jedyny znak iteratora (bądź nic, jeśli znaków jest więcej)
+fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None
+    }
+}
+
+/// Konwersja alfabetu na ciąg znaków
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbols.iter().collect::<String>())
+    }
+}
@@ -0,0 +1,226 @@
+use std::{fmt, io};
+
+/// Błędy zwracane przez bibliotekę
+#[derive(Debug)]
+pub enum Error {
+    /// Błąd odczytu bądź zapisu pliku z kluczem
+    KeyIo {
+        /// Ścieżka pliku
+        path: String,
+        /// Błąd zwrócony przez system
+        source: io::Error
+    },
+    /// Klucz posiada zbyt mało pozycji
+    KeyTooShort {
+        /// Wymagana ilość pozycji
+        expected: usize,
+        /// Ilość pozycji w kluczu
+        found: usize
+    },
+    /// Klucz zawiera powtarzające się znaki
+    NonUniqueKey {
+        /// Powtórzony znak
+        character: char,
+        /// Numer linijki (bądź kolumny) klucza, w której znak powtórzył się po raz pierwszy, liczony od 1
+        line: usize
+    },
+    /// Klucz zawiera znak spoza alfabetu
+    NonAlphabeticKey {
+        /// Niepoprawny znak
+        character: char,
+        /// Numer linijki (bądź kolumny) klucza, liczony od 1
+        line: usize
+    },
+    /// Pozycja klucza nie jest permutacją alfabetu
+    NotPermutation {
+        /// Symbol alfabetu powtórzony bądź pominięty w pozycji klucza
+        character: char,
+        /// Numer linijki (bądź kolumny) klucza, liczony od 1
+        line: usize
+    },
+    /// Alfabet nie zawiera żadnych symboli
+    EmptyAlphabet,
+    /// Alfabet zawiera powtarzający się symbol
+    DuplicateSymbol {
+        /// Powtórzony symbol
+        character: char
+    },
+    /// Błąd odczytu bądź zapisu strumienia danych
+    Io(io::Error),
+    /// Strumień danych nie jest poprawnym tekstem UTF-8
+    InvalidUtf8 {
+        /// Pozycja pierwszego niepoprawnego bajtu
+        position: usize
+    },
+    /// Szyfrogram zawiera znak, którego nie ma w kluczu
+    UnknownSymbol {
+        /// Nieznany znak
+        character: char,
+        /// Pozycja znaku w tekście (liczona w znakach od 0)
+        position: usize
+    },
+    /// Niepoprawna linijka nagłówka pliku klucza
+    InvalidKeyHeader {
+        /// Numer linijki pliku, liczony od 1
+        line: usize
+    },
+    /// Nieobsługiwana wersja formatu pliku klucza
+    UnsupportedKeyVersion {
+        /// Wersja zapisana w pliku
+        version: u32
+    },
+    /// Linijka klucza z symbolami tekstu jawnego nie ma postaci `symbol: homofony`
+    MalformedKeyLine {
+        /// Numer linijki pliku, liczony od 1
+        line: usize
+    },
+    /// Suma kontrolna zapisana w nagłówku nie zgadza się z kluczem
+    ChecksumMismatch {
+        /// Suma kontrolna z nagłówka
+        expected: u64,
+        /// Suma kontrolna wyliczona z klucza
+        found: u64
+    },
+    /// Symbol pusty jest jednocześnie homofonem
+    NullCollision {
+        /// Symbol pusty
+        character: char
+    },
+    /// Pula symboli jest zbyt mała, by przydzielić co najmniej jeden symbol każdej pozycji klucza
+    InsufficientSymbols {
+        /// Wymagana ilość symboli
+        required: usize,
+        /// Ilość dostępnych symboli
+        found: usize
+    },
+    /// Linijka książki kodowej nie ma postaci `słowo: grupy kodowe`
+    MalformedCodebookLine {
+        /// Numer linijki pliku, liczony od 1
+        line: usize
+    },
+    /// Słowo powtarza się w książce kodowej
+    DuplicateCodeword {
+        /// Powtórzone słowo
+        word: String,
+        /// Numer linijki pliku, w której słowo powtórzyło się, liczony od 1
+        line: usize
+    },
+    /// Grupa kodowa nie może zostać jednoznacznie odczytana (jest prefiksem innej grupy,
+    /// zaczyna się od homofonu bądź symbolu pustego lub zawiera biały znak)
+    AmbiguousCodeGroup {
+        /// Niejednoznaczna grupa kodowa
+        group: String
+    },
+    /// Nieobsługiwana długość kodów liczbowych
+    InvalidCodeWidth {
+        /// Ilość cyfr kodu
+        width: usize
+    },
+    /// Kod liczbowy nie mieści się w podanej ilości cyfr
+    CodeOutOfRange {
+        /// Niepoprawny kod
+        code: u32,
+        /// Ilość cyfr kodu
+        width: usize
+    },
+    /// Kod liczbowy przypisany jest więcej niż raz
+    DuplicateCode {
+        /// Powtórzony kod
+        code: u32
+    },
+    /// Szyfrogram zawiera kod liczbowy, którego nie ma w kluczu
+    UnknownCode {
+        /// Nieznany kod (w zapisie z szyfrogramu)
+        code: String,
+        /// Pozycja pierwszej cyfry kodu w tekście (liczona w znakach od 0)
+        position: usize
+    },
+    /// Mnożnik szyfru afinicznego nie jest względnie pierwszy z długością alfabetu
+    NotCoprime {
+        /// Podany mnożnik
+        multiplier: i64,
+        /// Długość alfabetu
+        modulus: usize
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::KeyIo { path, source } => 
+                write!(f, "Key I/O error! Could not access {}: {}", path, source),
+            Error::KeyTooShort { expected, found } => 
+                write!(f, "Invalid code! Valid code must contain at least {} characters, found {}", expected, found),
+            Error::NonUniqueKey { character, line } => 
+                write!(f, "Invalid code! Valid code must contain unique characters ({:?} repeated at line {})", character, line),
+            Error::NonAlphabeticKey { character, line } => 
+                write!(f, "Invalid code! Valid code must contain characters from the alphabet ({:?} at line {})", character, line),
+            Error::NotPermutation { character, line } => 
+                write!(f, "Invalid code! Line {} must contain every symbol of the alphabet exactly once ({:?} is repeated or missing)", line, character),
+            Error::EmptyAlphabet => 
+                write!(f, "Invalid alphabet! Valid alphabet must contain at least one symbol"),
+            Error::DuplicateSymbol { character } => 
+                write!(f, "Invalid alphabet! Symbol {:?} is repeated", character),
+            Error::Io(source) => 
+                write!(f, "I/O error! {}", source),
+            Error::InvalidUtf8 { position } => 
+                write!(f, "Invalid data! Stream is not valid UTF-8 at byte {}", position),
+            Error::UnknownSymbol { character, position } => 
+                write!(f, "Invalid data! Symbol {:?} at position {} is not present in the code", character, position),
+            Error::InvalidKeyHeader { line } => 
+                write!(f, "Invalid key file! Malformed header at line {}", line),
+            Error::UnsupportedKeyVersion { version } => 
+                write!(f, "Invalid key file! Unsupported format version {}", version),
+            Error::MalformedKeyLine { line } => 
+                write!(f, "Invalid key file! Line {} must have form \"symbol: homophones\"", line),
+            Error::ChecksumMismatch { expected, found } => 
+                write!(f, "Invalid key file! Checksum {:016x} does not match key ({:016x})", expected, found),
+            Error::NullCollision { character } => 
+                write!(f, "Invalid code! Null symbol {:?} is also used as homophone", character),
+            Error::InsufficientSymbols { required, found } => 
+                write!(f, "Invalid symbol pool! At least {} unique symbols are required, found {}", required, found),
+            Error::MalformedCodebookLine { line } => 
+                write!(f, "Invalid codebook! Line {} must have form \"word: code groups\"", line),
+            Error::DuplicateCodeword { word, line } => 
+                write!(f, "Invalid codebook! Word {:?} is repeated at line {}", word, line),
+            Error::AmbiguousCodeGroup { group } => 
+                write!(f, "Invalid codebook! Code group {:?} cannot be decoded unambiguously", group),
+            Error::InvalidCodeWidth { width } => 
+                write!(f, "Invalid code! Numeric codes must have from 1 to 4 digits, found {}", width),
+            Error::CodeOutOfRange { code, width } => 
+                write!(f, "Invalid code! Numeric code {} does not fit in {} digits", code, width),
+            Error::DuplicateCode { code } => 
+                write!(f, "Invalid code! Numeric code {} is assigned more than once", code),
+            Error::UnknownCode { code, position } => 
+                write!(f, "Invalid data! Numeric code {} at position {} is not present in the code", code, position),
+            Error::NotCoprime { multiplier, modulus } => 
+                write!(f, "Invalid key! Multiplier {} must be coprime with alphabet size {}", multiplier, modulus)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::KeyIo { source, .. } | Error::Io(source) => Some(source),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Error {
+        Error::Io(source)
+    }
+}
+
+impl Error {
+    /// Przesuwa pozycję zapisaną w błędzie o podaną ilość znaków (dla fragmentów dłuższego tekstu)
+    pub(crate) fn shifted(self, characters: usize) -> Error {
+        match self {
+            Error::UnknownSymbol { character, position } => Error::UnknownSymbol { character, position: position + characters },
+            Error::UnknownCode { code, position } => Error::UnknownCode { code, position: position + characters },
+            other => other
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::Alphabet;
+
+/// Język tekstu jawnego
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Język angielski (alfabet łaciński, 26 liter)
+    English,
+    /// Język polski (alfabet polski, 32 litery)
+    Polish
+}
+
+/// Metody języka
+impl Language {
+    /// Zwraca język o podanej nazwie
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - nazwa bądź kod języka (`english`, `en`, `polish`, `pl`)
+    pub fn parse(name: &str) -> Option<Language> {
+        match name.trim().to_lowercase().as_str() {
+            "english" | "en" => Some(Language::English),
+            "polish" | "pl" => Some(Language::Polish),
+            _ => None
+        }
+    }
+
+    /// Wielkie litery języka (małe litery obsługiwane są z zachowaniem wielkości,
+    /// odpowiednie dla szyfru cezara i szyfru Vigenère'a)
+    pub fn alphabet(&self) -> Alphabet {
+        match self {
+            Language::English => Alphabet::latin_upper(),
+            Language::Polish => Alphabet::polish()
+        }
+    }
+
+    /// Wielkie i małe litery języka jako osobne symbole (odpowiednie dla szyfru homofonicznego)
+    pub fn mixed_alphabet(&self) -> Alphabet {
+        match self {
+            Language::English => Alphabet::latin_mixed(),
+            Language::Polish => Alphabet::polish_mixed()
+        }
+    }
+
+    /// Częstość występowania (w procentach) kolejnych liter z [`Language::alphabet`] w typowym tekście
+    pub fn frequencies(&self) -> &'static [f64] {
+        match self {
+            Language::English => &ENGLISH_FREQUENCIES,
+            Language::Polish => &POLISH_FREQUENCIES
+        }
+    }
+
+    /// Częstość występowania kolejnych symboli z [`Language::mixed_alphabet`]
+    /// (wielka i mała litera otrzymują tę samą wagę)
+    pub fn mixed_frequencies(&self) -> Vec<f64> {
+        self.frequencies().iter().chain(self.frequencies()).copied().collect()
+    }
+}
+
+/// Częstość liter A-Z w tekstach angielskich
+const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.167, 1.492, 2.782, 4.253, 12.702, 2.228, 2.015, 6.094, 6.966, 0.153, 0.772, 4.025, 2.406,
+    6.749, 7.507, 1.929, 0.095, 5.987, 6.327, 9.056, 2.758, 0.978, 2.360, 0.150, 1.974, 0.074
+];
+
+/// Częstość liter AĄBCĆDEĘFGHIJKLŁMNŃOÓPRSŚTUWYZŹŻ w tekstach polskich
+const POLISH_FREQUENCIES: [f64; 32] = [
+    8.91, 0.99, 1.47, 3.96, 0.40, 3.25, 7.66, 1.11, 0.30, 1.42, 1.08, 8.21, 2.28, 3.51, 2.10, 1.82,
+    2.80, 5.52, 0.20, 7.75, 0.85, 3.13, 4.69, 4.32, 0.66, 3.98, 2.50, 4.65, 3.76, 5.64, 0.06, 0.83
+];
@@ -1,2 +1,10 @@
 pub mod algorithms;
-pub mod utils;
\ No newline at end of file
+pub mod alphabet;
+pub mod error;
+pub mod language;
+pub mod stream;
+pub mod utils;
+
+pub use alphabet::Alphabet;
+pub use error::Error;
+pub use language::Language;
\ No newline at end of file
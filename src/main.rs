@@ -1,13 +1,81 @@
-use std::io::Stdin;
-use std::{io, error::Error};
-use szyfry::{algorithms::*, utils::*};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::io::{BufWriter, Read, Stdin, Write};
+use std::{env, io, process, error::Error};
+use rand::{RngCore, SeedableRng, rngs::StdRng};
+use szyfry::{algorithms::*, stream, utils::*};
+use szyfry::{Alphabet, Language, Error as CipherError};
+
+/// Opis dostępnych komend wyświetlany przy błędnym wywołaniu programu
+const USAGE: &str = "Usage:
+    szyfry                       run interactive mode
+    szyfry encrypt [options]     encrypt text
+    szyfry decrypt [options]     decrypt text
+    szyfry keygen [options]      generate new key
+    szyfry inspect [options]     print information about key
+    szyfry help                  print this message
+
+Options:
+    --cipher <ceasar|homophonic|polyalphabetic|numeric|affine>
+                                 keygen: generate key always valid for given cipher
+    --offset <number|letter>     offset for ceasar cipher (negative shifts left, letter A = 0)
+                                 affine: offset b in E(x) = (a*x + b) mod m (number, default: 0)
+    --multiplier <number>        affine: multiplier a, coprime with alphabet size (1 - ceasar, -1 with offset -1 - atbash)
+    --alphabet <name|path>       latin-upper, latin-mixed, polish, polish-mixed, digits, ascii or path to alphabet file
+    --lang <en|pl>               use alphabet of given language (ignored when --alphabet is given)
+                                 keygen: homophonic key with homophones proportional to letter frequency
+    --key <path>                 path to key (or name of key in directories from SZYFRY_KEYS, default: translation_keys)
+                                 numeric: lines 'symbol: codes', e.g. 'A: 07 31 64' (.nkey)
+    --keyword <word>             polyalphabetic: use keyword instead of key file (default alphabet: latin-upper)
+    --rule <column|row|labeled>  how to handle key without header (default: from extension .ckey/.lkey/.hkey, otherwise row)
+                                 labeled: every row starts with plaintext symbol, e.g. 'A: xQ7' (homophonic only)
+    --binary                     process raw bytes (ceasar and polyalphabetic only)
+    --input <path>               read text from file (default: stdin)
+    --output <path>              write result to file (default: stdout)
+    --substitution               polyalphabetic: every key position is a full substitution alphabet (also for keygen)
+    --advance <every|letters|alphabet>
+                                 polyalphabetic: when to move to next key position (default: every)
+    --seed <number>              keygen, homophonic encryption: seed for random generator (reproducible output)
+    --strategy <random|round-robin|lru|deterministic>
+                                 homophonic: how to choose homophones (default: random, deterministic uses --seed)
+    --unmapped <reject|keep|char>
+                                 homophonic encryption: how to handle symbols without homophones (default: keep, homophones, nulls and numeric digits are never kept)
+    --nulls <symbols>            homophonic: null symbols (default: declared in key header)
+    --null-rate <0-1>            homophonic encryption: insert null after each symbol with given probability
+    --null-every <number>        homophonic encryption: insert null after every n-th symbol
+    --null-count <number>        keygen: amount of null symbols declared in homophonic key header (default: 0)
+    --format <spaced|fixed>      numeric: codes separated by spaces (plaintext space as empty code) or without separators
+    --width <number>             keygen: digits of numeric codes, 1-4 (default: 2)
+    --codebook <path>            homophonic: nomenclator codebook (.cbook), lines 'word: code groups', e.g. 'king: §1 §2'
+    --unknown <reject|keep|char> decrypt: how to handle symbols missing from key (default: reject)
+    --count <number>             keygen: amount of key positions (default: 52, polyalphabetic: 8)
+    --subs <number>              keygen: max characters per position (default: 3)
+    --chars <characters>         keygen: characters to use (default: printable ascii)
+    --non-unique                 keygen: allow repeated characters";
 
 fn main() {
-    println!("Welcome to cipher program!\n");
-    while prompt_program() {
-        println!("Welcome back!\n")
-    } 
-    println!("See you soon!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() {
+        println!("Welcome to cipher program!\n");
+        while prompt_program() {
+            println!("Welcome back!\n")
+        } 
+        println!("See you soon!");
+        return;
+    }
+
+    let arguments: Arguments = match Arguments::parse(&args) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(error) = run_command(&arguments) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
 }
 
 /// Do określania czy należy zaszyfrować czy odszyfrować tekst
@@ -16,33 +84,604 @@ enum OperationName {
     /// Szyfrowanie
     Encryption, 
     /// Odszyfrowywanie
-    Decryption
+    Decryption,
+    /// Generowanie klucza
+    KeyGeneration
 }
 
 /// Do określenia jaki algorytm wykorzystać
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum CipherName {
     /// Szyfr cezara
     Ceasar,
     /// Szyfr homofoniczny
     Homophonic,
     /// Szyfr wieloalfabetowy (Vigenère'a)
-    Polyalphabetic
+    Polyalphabetic,
+    /// Szyfr homofoniczny z kodami liczbowymi
+    Numeric,
+    /// Szyfr afiniczny
+    Affine
+}
+
+/// Interpretuje nazwę algorytmu (pełną, skróconą bądź numer z menu)
+fn parse_cipher_name(name: &str) -> Option<CipherName> {
+    match name.trim().to_lowercase().as_str() {
+        "1" | "ceasar" | "c" => Some(CipherName::Ceasar),
+        "2" | "homophonic" | "h" => Some(CipherName::Homophonic),
+        "3" | "polyalphabetic" | "p" => Some(CipherName::Polyalphabetic),
+        "4" | "numeric" | "n" => Some(CipherName::Numeric),
+        "5" | "affine" | "a" => Some(CipherName::Affine),
+        _ => None
+    }
+}
+
+/// Interpretuje sposób odczytu klucza (pełną, skróconą nazwę bądź numer z menu)
+fn parse_key_rule(name: &str) -> Option<KeyRule> {
+    match name.trim().to_lowercase().as_str() {
+        "1" | "column" | "c" => Some(KeyRule::Column),
+        "2" | "row" | "r" => Some(KeyRule::Row),
+        "3" | "labeled" | "l" => Some(KeyRule::Labeled),
+        _ => None
+    }
+}
+
+/// Komendy dostępne z linii poleceń
+enum Command {
+    /// Szyfrowanie
+    Encrypt,
+    /// Odszyfrowywanie
+    Decrypt,
+    /// Generowanie klucza
+    Keygen,
+    /// Wyświetlanie informacji o kluczu
+    Inspect,
+    /// Wyświetlanie pomocy
+    Help
+}
+
+/// Argumenty przekazane z linii poleceń
+struct Arguments {
+    /// Komenda do wykonania
+    command: Command,
+    /// Opcje komendy (nazwa bez `--` i wartość)
+    options: HashMap<String, String>
+}
+
+/// Opcje nie przyjmujące wartości
+const SWITCHES: [&str; 3] = ["non-unique", "substitution", "binary"];
+
+/// Metody argumentów linii poleceń
+impl Arguments {
+    /// Interpretuje argumenty linii poleceń
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - argumenty programu (bez nazwy programu)
+    /// 
+    /// # Errors
+    /// 
+    /// Nieznana komenda, nieznana opcja bądź opcja bez wartości
+    fn parse(args: &[String]) -> Result<Arguments, String> {
+        let (command, allowed): (Command, &[&str]) = match args[0].as_str() {
+            "encrypt" => (Command::Encrypt, &["cipher", "offset", "multiplier", "alphabet", "lang", "key", "keyword", "substitution", "rule", "advance", "binary", "seed", "strategy", "unmapped", "nulls", "null-rate", "null-every", "codebook", "format", "input", "output"]),
+            "decrypt" => (Command::Decrypt, &["cipher", "offset", "multiplier", "alphabet", "lang", "key", "keyword", "substitution", "rule", "advance", "binary", "nulls", "codebook", "format", "input", "output", "unknown"]),
+            "keygen" => (Command::Keygen, &["cipher", "alphabet", "lang", "rule", "count", "subs", "chars", "non-unique", "substitution", "null-count", "width", "seed", "output"]),
+            "inspect" => (Command::Inspect, &["key", "rule"]),
+            "help" | "--help" | "-h" => (Command::Help, &[]),
+            other => return Err(format!("UNKNOWN COMMAND: {}", other))
+        };
+
+        let mut options: HashMap<String, String> = HashMap::new();
+        let mut iter = args[1..].iter();
+        while let Some(arg) = iter.next() {
+            let name: &str = arg.strip_prefix("--")
+                                .ok_or_else(|| format!("UNEXPECTED ARGUMENT: {}", arg))?;
+            let (name, value): (&str, String) = match name.split_once('=') {
+                Some((name, value)) => (name, value.to_string()),
+                None if SWITCHES.contains(&name) => (name, String::from("true")),
+                None => (name, iter.next()
+                                   .ok_or_else(|| format!("MISSING VALUE FOR OPTION: --{}", name))?
+                                   .clone())
+            };
+            if !allowed.contains(&name) {
+                return Err(format!("UNKNOWN OPTION: --{}", name));
+            }
+            options.insert(name.to_string(), value);
+        }
+        Ok(Arguments { command, options })
+    }
+
+    /// Zwraca wartość opcji bądź błąd jeśli nie została podana
+    fn required(&self, name: &str) -> Result<&str, Box<dyn Error>> {
+        self.options.get(name)
+                    .map(|value: &String| value.as_str())
+                    .ok_or_else(|| format!("MISSING OPTION: --{}", name).into())
+    }
+
+    /// Zwraca wartość liczbową opcji bądź wartość domyślną jeśli nie została podana
+    fn number(&self, name: &str, default: usize) -> Result<usize, Box<dyn Error>> {
+        match self.options.get(name) {
+            Some(value) => value.trim()
+                                .parse()
+                                .map_err(|_| format!("INVALID NUMBER FOR OPTION --{}: {}", name, value).into()),
+            None => Ok(default)
+        }
+    }
+
+    /// Zwraca ziarno generatora liczb losowych wskazane opcją `--seed`
+    fn seed(&self) -> Result<Option<u64>, Box<dyn Error>> {
+        match self.options.get("seed") {
+            Some(value) => value.trim()
+                                .parse()
+                                .map(Some)
+                                .map_err(|_| format!("INVALID NUMBER FOR OPTION --seed: {}", value).into()),
+            None => Ok(None)
+        }
+    }
+
+    /// Zwraca generator liczb losowych z ziarnem wskazanym opcją `--seed`
+    fn seeded_rng(&self) -> Result<Option<StdRng>, Box<dyn Error>> {
+        Ok(self.seed()?.map(StdRng::seed_from_u64))
+    }
+
+    /// Zwraca sposób odczytu klucza (domyślnie linijka po linijce)
+    fn rule(&self) -> Result<KeyRule, Box<dyn Error>> {
+        Ok(self.explicit_rule()?.unwrap_or(KeyRule::Row))
+    }
+
+    /// Zwraca sposób odczytu klucza wskazany opcją `--rule` (`None` - wykrycie z nagłówka bądź rozszerzenia pliku)
+    fn explicit_rule(&self) -> Result<Option<KeyRule>, Box<dyn Error>> {
+        match self.options.get("rule") {
+            Some(value) => parse_key_rule(value)
+                            .map(Some)
+                            .ok_or_else(|| format!("INVALID OPTION: --rule {}\nVALID OPTIONS ARE: \n\tcolumn, c\n\trow, r\n\tlabeled, l", value).into()),
+            None => Ok(None)
+        }
+    }
+
+    /// Zwraca sposób obsługi nieznanych znaków przy odszyfrowywaniu (domyślnie zgłaszanie błędu)
+    fn unknown(&self) -> Result<Unknown, Box<dyn Error>> {
+        self.policy("unknown", Unknown::Reject)
+    }
+
+    /// Zwraca sposób obsługi znaków bez homofonów przy szyfrowaniu (domyślnie przepisywanie bez zmian)
+    fn unmapped(&self) -> Result<Unknown, Box<dyn Error>> {
+        self.policy("unmapped", Unknown::Keep)
+    }
+
+    /// Zwraca sposób obsługi znaków wskazany podaną opcją
+    fn policy(&self, option: &str, default: Unknown) -> Result<Unknown, Box<dyn Error>> {
+        match self.options.get(option).map(|value: &String| value.as_str()) {
+            None => Ok(default),
+            Some("reject") => Ok(Unknown::Reject),
+            Some("keep") => Ok(Unknown::Keep),
+            Some(value) if value.chars().count() == 1 => Ok(Unknown::Replace(value.chars().next().unwrap())),
+            Some(value) => Err(format!("INVALID OPTION: --{} {}\nVALID OPTIONS ARE: \n\treject\n\tkeep\n\tsingle placeholder character", option, value).into())
+        }
+    }
+
+    /// Zwraca sposób przechodzenia do kolejnej pozycji klucza (domyślnie po każdym znaku)
+    fn advance(&self) -> Result<KeyAdvance, Box<dyn Error>> {
+        match self.options.get("advance").map(|value: &String| value.as_str()) {
+            None | Some("every") => Ok(KeyAdvance::EveryCharacter),
+            Some("letters") => Ok(KeyAdvance::Alphabetic),
+            Some("alphabet") => Ok(KeyAdvance::AlphabetSymbol),
+            Some(value) => Err(format!("INVALID OPTION: --advance {}\nVALID OPTIONS ARE: \n\tevery\n\tletters\n\talphabet", value).into())
+        }
+    }
+
+    /// Zwraca sposób wstawiania symboli pustych wskazany opcją `--null-rate` bądź `--null-every`
+    fn null_placement(&self) -> Result<NullPlacement, Box<dyn Error>> {
+        match (self.options.get("null-rate"), self.options.get("null-every")) {
+            (Some(_), Some(_)) => Err("INVALID OPTION: --null-rate and --null-every cannot be used together".into()),
+            (Some(rate), None) => match rate.trim().parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(NullPlacement::Random { rate }),
+                _ => Err(format!("INVALID NUMBER FOR OPTION --null-rate: {} (expected value from 0 to 1)", rate).into())
+            },
+            (None, Some(_)) => Ok(NullPlacement::Interval { every: self.number("null-every", 0)? }),
+            (None, None) => Ok(NullPlacement::None)
+        }
+    }
+
+    /// Zwraca sposób wyboru homofonów (domyślnie losowo, strategia deterministyczna korzysta z `--seed`)
+    fn strategy(&self) -> Result<HomophoneStrategy, Box<dyn Error>> {
+        match self.options.get("strategy").map(|value: &String| value.as_str()) {
+            None | Some("random") => Ok(HomophoneStrategy::Random),
+            Some("round-robin") => Ok(HomophoneStrategy::RoundRobin),
+            Some("lru") => Ok(HomophoneStrategy::LeastRecentlyUsed),
+            Some("deterministic") => Ok(HomophoneStrategy::Deterministic { seed: self.seed()?.unwrap_or(0) }),
+            Some(value) => Err(format!("INVALID OPTION: --strategy {}\nVALID OPTIONS ARE: \n\trandom\n\tround-robin\n\tlru\n\tdeterministic", value).into())
+        }
+    }
+
+    /// Zwraca alfabet wskazany opcją `--alphabet` (nazwa wbudowanego alfabetu bądź ścieżka do pliku,
+    /// plik nieistniejący w podanej ścieżce wyszukiwany jest w katalogach kluczy)
+    fn alphabet(&self) -> Result<Option<Alphabet>, Box<dyn Error>> {
+        match self.options.get("alphabet") {
+            Some(name) => match Alphabet::preset(name) {
+                Some(alphabet) => Ok(Some(alphabet)),
+                None if Path::new(name).exists() => Ok(Some(Alphabet::read(name)?)),
+                None => Ok(Some(Alphabet::read_translation(name)?))
+            },
+            None => Ok(None)
+        }
+    }
+
+    /// Zwraca język wskazany opcją `--lang`
+    fn language(&self) -> Result<Option<Language>, Box<dyn Error>> {
+        match self.options.get("lang") {
+            Some(name) => Language::parse(name)
+                            .map(Some)
+                            .ok_or_else(|| format!("INVALID OPTION: --lang {}\nVALID OPTIONS ARE: \n\ten\n\tpl", name).into()),
+            None => Ok(None)
+        }
+    }
+
+    /// Odczytuje klucz wskazany opcją `--key` (plik nieistniejący w podanej ścieżce wyszukiwany jest w katalogach kluczy)
+    fn key(&self) -> Result<Key, Box<dyn Error>> {
+        let path: &str = self.required("key")?;
+        match Path::new(path).exists() {
+            true => Ok(Key::read(path, self.explicit_rule()?)?),
+            false => Ok(Key::read_translation(path, self.explicit_rule()?)?)
+        }
+    }
+
+    /// Odczytuje klucz z kodami liczbowymi wskazany opcją `--key` (wyszukiwany jak klucz)
+    fn numeric_key(&self) -> Result<NumericKey, Box<dyn Error>> {
+        let path: &str = self.required("key")?;
+        match Path::new(path).exists() {
+            true => Ok(NumericKey::read(path)?),
+            false => Ok(NumericKey::read_translation(path)?)
+        }
+    }
+
+    /// Interpretuje opcję `--format` (domyślnie kody rozdzielone spacjami)
+    fn format(&self) -> Result<NumericFormat, Box<dyn Error>> {
+        match self.options.get("format").map(|v: &String| v.trim().to_lowercase()).as_deref() {
+            None | Some("spaced") | Some("s") => Ok(NumericFormat::Spaced),
+            Some("fixed") | Some("f") => Ok(NumericFormat::Fixed),
+            Some(other) => Err(format!("INVALID OPTION: --format {}\nVALID OPTIONS ARE: \n\tspaced, s\n\tfixed, f", other).into())
+        }
+    }
+
+    /// Odczytuje książkę kodową wskazaną opcją `--codebook` (wyszukiwaną jak klucz)
+    fn codebook(&self) -> Result<Option<Codebook>, Box<dyn Error>> {
+        match self.options.get("codebook") {
+            Some(path) if Path::new(path).exists() => Ok(Some(Codebook::read(path)?)),
+            Some(path) => Ok(Some(Codebook::read_translation(path)?)),
+            None => Ok(None)
+        }
+    }
+}
+
+/// Wykonuje komendę podaną w linii poleceń
+fn run_command(arguments: &Arguments) -> Result<(), Box<dyn Error>> {
+    match arguments.command {
+        Command::Encrypt | Command::Decrypt if arguments.options.contains_key("binary") => {
+            let algorithm: Box<dyn ByteCipher> = build_byte_cipher(arguments)?;
+            let (reader, writer): (Box<dyn Read>, Box<dyn Write>) = (open_input(arguments)?, open_output(arguments)?);
+            match arguments.command {
+                Command::Encrypt => stream::encrypt_bytes_stream(algorithm.as_ref(), reader, writer)?,
+                _ => stream::decrypt_bytes_stream(algorithm.as_ref(), reader, writer)?
+            };
+            Ok(())
+        },
+        Command::Encrypt | Command::Decrypt => {
+            let algorithm: Box<dyn Cipher> = build_cipher(arguments)?;
+            let (reader, writer): (Box<dyn Read>, Box<dyn Write>) = (open_input(arguments)?, open_output(arguments)?);
+            match arguments.command {
+                Command::Encrypt => stream::encrypt_stream(algorithm.as_ref(), reader, writer)?,
+                _ => stream::decrypt_stream(algorithm.as_ref(), reader, writer)?
+            };
+            Ok(())
+        },
+        Command::Keygen => write_output(arguments, &generate_key(arguments)?),
+        Command::Inspect => {
+            let key: Key = arguments.key()?;
+            print!("{}", describe_key(&key));
+            Ok(())
+        },
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    }
+}
+
+/// Tworzy algorytm na podstawie opcji linii poleceń
+fn build_cipher(arguments: &Arguments) -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+    let name: &str = arguments.required("cipher")?;
+    let cipher: CipherName = parse_cipher_name(name)
+        .ok_or_else(|| format!("INVALID OPTION: --cipher {}\nVALID OPTIONS ARE: \n\tceasar, c\n\thomophonic, h\n\tpolyalphabetic, p\n\tnumeric, n\n\taffine, a", name))?;
+    let alphabet: Option<Alphabet> = arguments.alphabet()?;
+    let language: Option<Language> = arguments.language()?;
+    Ok(match cipher {
+        CipherName::Ceasar => {
+            let alphabet: Alphabet = alphabet.or_else(|| language.map(|l: Language| l.alphabet()))
+                                             .unwrap_or_else(Alphabet::latin_upper);
+            Box::new(parse_ceasar(arguments.required("offset")?, alphabet)?)
+        },
+        CipherName::Homophonic => {
+            let code: Key = arguments.key()?;
+            let alphabet: Option<Alphabet> = key_alphabet(alphabet.or_else(|| language.map(|l: Language| l.mixed_alphabet())), &code)?;
+            let mut algorithm: HomophonicData = match alphabet {
+                Some(alphabet) => HomophonicData::with_alphabet(code, alphabet)?,
+                None => HomophonicData::new(code)?
+            };
+            if let Some(nulls) = arguments.options.get("nulls") {
+                algorithm = algorithm.null_symbols(nulls.chars().filter(|c: &char| !c.is_whitespace()).collect())?;
+            }
+            algorithm = algorithm.unknown(arguments.unknown()?)
+                                 .unmapped(arguments.unmapped()?)
+                                 .null_placement(arguments.null_placement()?)
+                                 .strategy(arguments.strategy()?);
+            if let Some(rng) = arguments.seeded_rng()? {
+                algorithm = algorithm.rng(rng);
+            }
+            match arguments.codebook()? {
+                Some(codebook) => Box::new(NomenclatorData::new(algorithm, codebook)?),
+                None => Box::new(algorithm)
+            }
+        },
+        CipherName::Polyalphabetic => {
+            let (code, default): (Key, fn() -> Alphabet) = match arguments.options.get("keyword") {
+                Some(keyword) => (Key::from_keyword(keyword), Alphabet::latin_upper),
+                None => (arguments.key()?, Alphabet::latin_mixed)
+            };
+            let alphabet: Alphabet = key_alphabet(alphabet.or_else(|| language.map(|l: Language| l.alphabet())), &code)?
+                                             .unwrap_or_else(default);
+            let algorithm: PolyalphabeticData = match arguments.options.contains_key("substitution") {
+                true => PolyalphabeticData::substitution(code, alphabet)?,
+                false => PolyalphabeticData::with_alphabet(code, alphabet)?
+            };
+            Box::new(algorithm.advance(arguments.advance()?))
+        },
+        CipherName::Affine => {
+            let alphabet: Alphabet = alphabet.or_else(|| language.map(|l: Language| l.alphabet()))
+                                             .unwrap_or_else(Alphabet::latin_upper);
+            let multiplier: i64 = parse_integer("multiplier", arguments.required("multiplier")?)?;
+            let offset: i64 = match arguments.options.get("offset") {
+                Some(offset) => parse_integer("offset", offset)?,
+                None => 0
+            };
+            Box::new(AffineData::with_alphabet(multiplier, offset, alphabet)?)
+        },
+        CipherName::Numeric => {
+            let mut algorithm: NumericHomophonicData = NumericHomophonicData::new(arguments.numeric_key()?)?
+                .format(arguments.format()?)
+                .unknown(arguments.unknown()?)
+                .unmapped(arguments.unmapped()?)
+                .strategy(arguments.strategy()?);
+            if let Some(rng) = arguments.seeded_rng()? {
+                algorithm = algorithm.rng(rng);
+            }
+            Box::new(algorithm)
+        }
+    })
+}
+
+/// Zwraca alfabet wskazany opcją `--alphabet` bądź `--lang`, a w przypadku ich braku alfabet z nagłówka klucza
+/// (alfabet wskazany opcją musi być zgodny z alfabetem z nagłówka)
+fn key_alphabet(explicit: Option<Alphabet>, code: &Key) -> Result<Option<Alphabet>, Box<dyn Error>> {
+    match (explicit, code.alphabet().cloned()) {
+        (Some(explicit), Some(declared)) if explicit != declared => {
+            Err(format!("INVALID OPTION: --alphabet or --lang does not match key alphabet: {}", 
+                        declared.name().map_or_else(|| declared.to_string(), String::from)).into())
+        },
+        (explicit, declared) => Ok(explicit.or(declared))
+    }
+}
+
+/// Tworzy algorytm działający na bajtach na podstawie opcji linii poleceń
+fn build_byte_cipher(arguments: &Arguments) -> Result<Box<dyn ByteCipher>, Box<dyn Error>> {
+    let name: &str = arguments.required("cipher")?;
+    match parse_cipher_name(name) {
+        Some(CipherName::Ceasar) => {
+            let offset: i64 = parse_integer("offset", arguments.required("offset")?)?;
+            Ok(Box::new(ByteCeasarData::new(offset)))
+        },
+        Some(CipherName::Polyalphabetic) => match arguments.options.get("keyword") {
+            Some(keyword) => Ok(Box::new(BytePolyalphabeticData::new(keyword.as_bytes().to_vec())?)),
+            None => Ok(Box::new(BytePolyalphabeticData::from_key(&arguments.key()?)?))
+        },
+        _ => Err(format!("INVALID OPTION: --cipher {}\nVALID OPTIONS FOR --binary ARE: \n\tceasar, c\n\tpolyalphabetic, p", name).into())
+    }
+}
+
+/// Generuje klucz na podstawie opcji linii poleceń
+/// i zwraca jego zawartość w formacie pliku (dla `--cipher` klucz jest zawsze poprawny dla danego szyfru i zapisywany z nagłówkiem)
+fn generate_key(arguments: &Arguments) -> Result<String, Box<dyn Error>> {
+    let subs: usize = arguments.number("subs", 3)?;
+    if subs == 0 {
+        return Err("INVALID OPTION: --subs must be greater than 0".into());
+    }
+    let chars: Vec<char> = match arguments.options.get("chars") {
+        Some(chars) => chars.chars().filter(|c: &char| !c.is_whitespace()).collect(),
+        None => ('!'..='~').collect()
+    };
+    let mut rng: Box<dyn RngCore> = match arguments.seeded_rng()? {
+        Some(rng) => Box::new(rng),
+        None => Box::new(StdRng::from_entropy())
+    };
+    let rule: KeyRule = arguments.rule()?;
+    let (alphabet, language): (Option<Alphabet>, Option<Language>) = (arguments.alphabet()?, arguments.language()?);
+    let cipher: Option<CipherName> = match arguments.options.get("cipher") {
+        Some(name) => Some(parse_cipher_name(name)
+            .ok_or_else(|| format!("INVALID OPTION: --cipher {}\nVALID OPTIONS ARE: \n\thomophonic, h\n\tpolyalphabetic, p\n\tnumeric, n", name))?),
+        None => None
+    };
+    // klucz generowany z ziarnem nie zawiera czasu utworzenia, by plik klucza był powtarzalny
+    let header: KeyHeader = match arguments.seed()? {
+        Some(_) => KeyHeader::new().created(None),
+        None => KeyHeader::new()
+    };
+
+    let key: Result<Key, Box<dyn Error>> = match (cipher, alphabet, language) {
+        (Some(CipherName::Ceasar), _, _) => Err("INVALID OPTION: ceasar cipher does not use key file (use --offset)".into()),
+        (Some(CipherName::Affine), _, _) => {
+            Err("INVALID OPTION: affine cipher does not use key file (use --multiplier and --offset)".into())
+        },
+        (Some(CipherName::Numeric), _, _) => return Ok(generate_numeric_key(arguments)?.to_string()),
+        (Some(CipherName::Polyalphabetic) | None, _, _) if rule == KeyRule::Labeled => {
+            Err("INVALID OPTION: --rule labeled is supported only for --cipher homophonic".into())
+        },
+        (None | Some(CipherName::Homophonic), None, Some(language)) => {
+            let alphabet: Alphabet = language.mixed_alphabet();
+            let header: KeyHeader = header.cipher("homophonic").alphabet(&alphabet);
+            let key: Key = Key::generate_homophonic_with_rng(language, chars, &mut rng)?;
+            // bez `--rule` klucz zachowuje symbole tekstu jawnego
+            let key: Key = match arguments.explicit_rule()? {
+                Some(rule) => key.rule(rule),
+                None => key
+            };
+            Ok(key.header(header))
+        },
+        (Some(CipherName::Homophonic), alphabet, _) => {
+            let params: HomophonicKeyParams = HomophonicKeyParams { 
+                alphabet: alphabet.unwrap_or_else(Alphabet::latin_mixed), 
+                symbols: chars, 
+                max_homophones: subs, 
+                nulls: arguments.number("null-count", 0)?,
+                rule 
+            };
+            let key: Key = HomophonicData::generate_key_with_rng(&params, &mut rng)?;
+            let header: KeyHeader = header.cipher("homophonic")
+                                          .alphabet(&params.alphabet)
+                                          .nulls(key.nulls().to_vec());
+            Ok(key.header(header))
+        },
+        (Some(CipherName::Polyalphabetic), alphabet, language) => {
+            let params: PolyalphabeticKeyParams = PolyalphabeticKeyParams {
+                alphabet: alphabet.or_else(|| language.map(|l: Language| l.alphabet()))
+                                  .unwrap_or_else(Alphabet::latin_mixed),
+                length: arguments.number("count", 8)?,
+                substitution: arguments.options.contains_key("substitution")
+            };
+            let header: KeyHeader = header.cipher("polyalphabetic").alphabet(&params.alphabet);
+            Ok(PolyalphabeticData::generate_key_with_rng(&params, &mut rng)?.rule(rule).header(header))
+        },
+        (None, _, _) => {
+            let count: usize = arguments.number("count", 52)?;
+            let unique: bool = !arguments.options.contains_key("non-unique");
+            Ok(Key::generate_with_rng(rule, count, subs, chars, unique, &mut rng))
+        }
+    };
+    Ok(key?.to_file_string())
+}
+
+/// Generuje klucz z kodami liczbowymi na podstawie opcji linii poleceń
+/// (ilość kodów proporcjonalna do częstości liter języka, dla `--alphabet` równa dla wszystkich symboli)
+fn generate_numeric_key(arguments: &Arguments) -> Result<NumericKey, Box<dyn Error>> {
+    let width: usize = arguments.number("width", 2)?;
+    let language: Language = arguments.language()?.unwrap_or(Language::English);
+    let params: NumericKeyParams = match arguments.alphabet()? {
+        Some(alphabet) => NumericKeyParams { alphabet, frequencies: Vec::new(), width },
+        None => NumericKeyParams::for_language(language, width)
+    };
+    Ok(match arguments.seeded_rng()? {
+        Some(mut rng) => NumericHomophonicData::generate_key_with_rng(&params, &mut rng)?,
+        None => NumericHomophonicData::generate_key(&params)?
+    })
+}
+
+/// Tworzy szyfr cezara z przesunięcia podanego jako liczba bądź litera
+fn parse_ceasar(offset: &str, alphabet: Alphabet) -> Result<CeasarData, Box<dyn Error>> {
+    let offset: &str = offset.trim();
+    if let Ok(number) = offset.parse::<i64>() {
+        return Ok(CeasarData::with_alphabet(number, alphabet));
+    }
+    let mut chars = offset.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => Ok(CeasarData::from_letter_with_alphabet(letter, alphabet)?),
+        _ => Err(format!("INVALID OFFSET: {}\nOFFSET MUST BE A NUMBER OR A SINGLE LETTER", offset).into())
+    }
+}
+
+/// Interpretuje liczbę całkowitą podaną jako wartość opcji
+fn parse_integer(option: &str, value: &str) -> Result<i64, Box<dyn Error>> {
+    value.trim()
+         .parse()
+         .map_err(|_| format!("INVALID NUMBER FOR OPTION --{}: {}", option, value.trim()).into())
+}
+
+/// Otwiera plik wskazany opcją `--input` bądź standardowe wejście
+fn open_input(arguments: &Arguments) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    match arguments.options.get("input") {
+        Some(path) => Ok(Box::new(File::open(path)?)),
+        None => Ok(Box::new(io::stdin().lock()))
+    }
+}
+
+/// Otwiera plik wskazany opcją `--output` bądź standardowe wyjście
+fn open_output(arguments: &Arguments) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    match arguments.options.get("output") {
+        Some(path) => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        None => Ok(Box::new(BufWriter::new(io::stdout().lock())))
+    }
+}
+
+/// Zapisuje tekst do pliku wskazanego opcją `--output` bądź na standardowe wyjście
+fn write_output(arguments: &Arguments, data: &str) -> Result<(), Box<dyn Error>> {
+    let mut writer: Box<dyn Write> = open_output(arguments)?;
+    writer.write_all(data.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Tworzy opis klucza (do komendy `inspect`)
+fn describe_key(key: &Key) -> String {
+    let symbols: usize = key.data.iter().map(|v: &String| v.chars().count()).sum();
+    let longest: usize = key.data.iter().map(|v: &String| v.chars().count()).max().unwrap_or(0);
+    let rule: &str = match key.rule {
+        KeyRule::Row => "row",
+        KeyRule::Column => "column",
+        KeyRule::Labeled => "labeled"
+    };
+    let header: String = match key.metadata() {
+        Some(header) => format!("format: v{}\ncipher: {}\nalphabet: {}\ncreated: {}\n", 
+                                header.version, 
+                                header.cipher.as_deref().unwrap_or("-"), 
+                                header.alphabet.as_ref().map_or(String::from("-"), |a: &Alphabet| a.name().map_or_else(|| a.to_string(), String::from)),
+                                header.created.map_or(String::from("-"), |c: u64| c.to_string())),
+        None => String::from("format: legacy (no header)\n")
+    };
+    format!("{}rule: {}\npositions: {}\nsymbols: {}\nlongest position: {}\nunique: {}\nhomophonic: {}\npolyalphabetic: {}\n{}",
+            header, rule, key.data.len(), symbols, longest, key.unique,
+            validity(HomophonicData::new(key.clone()).err()),
+            validity(PolyalphabeticData::new(key.clone()).err()),
+            key.validate())
+}
+
+/// Opisuje wynik walidacji klucza dla danego algorytmu
+fn validity(error: Option<CipherError>) -> String {
+    match error {
+        Some(error) => format!("invalid ({})", error),
+        None => String::from("valid")
+    }
 }
 
 /// Uruchamia serię pytań konsolowych
 fn prompt_program() -> bool {
     let choice: OperationName = loop_error(prompt_choice);
     let cipher: CipherName = loop_error(prompt_cipher);
+    let language: Language = loop_error(prompt_language);
+    if let OperationName::KeyGeneration = choice {
+        loop_error(|| prompt_keygen(cipher, language));
+        return loop_error(prompt_repeat_program);
+    }
     let algorithm: Box<dyn Cipher> = match cipher {
-        CipherName::Ceasar => loop_error(prompt_ceasar_data),
-        CipherName::Homophonic => loop_error(prompt_homophonic_data),
-        CipherName::Polyalphabetic => loop_error(prompt_polyalphabetic_data)
+        CipherName::Ceasar => loop_error(|| prompt_ceasar_data(language)),
+        CipherName::Homophonic => loop_error(|| prompt_homophonic_data(language)),
+        CipherName::Polyalphabetic => loop_error(|| prompt_polyalphabetic_data(language)),
+        CipherName::Numeric => loop_error(prompt_numeric_data),
+        CipherName::Affine => loop_error(|| prompt_affine_data(language))
     };
     let plain_text: String = loop_error(prompt_data);
     let cipher_text: String = match choice {
         OperationName::Encryption => algorithm.encrypt(&plain_text),
-        OperationName::Decryption => algorithm.decrypt(&plain_text)
+        OperationName::Decryption => algorithm.decrypt(&plain_text),
+        OperationName::KeyGeneration => unreachable!("key generation returns before reading text")
     };
     println!("After {:#?} with {:#?}\n{}\ngives:\n{}", choice, cipher, plain_text, cipher_text);
     loop_error(prompt_repeat_program)
@@ -63,27 +702,37 @@ fn prompt_repeat_program() -> Result<bool, Box<dyn Error>> {
 
 /// Pobiera od użytkownika informację o tym czy zaszyfrować/ odszyfrować ciąg znaków
 fn prompt_choice() -> Result<OperationName, Box<dyn Error>> {
-    println!("Choose action\n1. encrypt\n2. decrypt");
+    println!("Choose action\n1. encrypt\n2. decrypt\n3. generate key");
     let mut buffer: String = String::new();
     io::stdin().read_line(&mut buffer)?;
     match buffer.trim().to_lowercase().as_str() {
         "1" | "encrypt" | "e" => Ok(OperationName::Encryption),
         "2" | "decrypt" | "d" => Ok(OperationName::Decryption),
-        _ => Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, encrypt, e\n\t2, decrypt, d", buffer).into())
+        "3" | "keygen" | "k" => Ok(OperationName::KeyGeneration),
+        _ => Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, encrypt, e\n\t2, decrypt, d\n\t3, keygen, k", buffer).into())
     } 
 }
 
 /// Pobiera od użytkownika informację o algorytmie do wykorzystania
 fn prompt_cipher() -> Result<CipherName, Box<dyn Error>> {
-    println!("Choose algorithm\n1. ceasar\n2. homophonic\n3. polyalphabetic");
+    println!("Choose algorithm\n1. ceasar\n2. homophonic\n3. polyalphabetic\n4. numeric\n5. affine");
     let mut buffer: String = String::new();
     io::stdin().read_line(&mut buffer)?;
-    match buffer.trim().to_lowercase().as_str() {
-        "1" | "ceasar" | "c" => Ok(CipherName::Ceasar),
-        "2" | "homophonic" | "h" => Ok(CipherName::Homophonic),
-        "3" | "polyalphabetic" | "p" => Ok(CipherName::Polyalphabetic),
-        _ => Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, ceasar, c\n\t2, homophonic, h\n\t3, polyalphabetic, p", buffer).into())
-    } 
+    parse_cipher_name(&buffer)
+        .ok_or_else(|| format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, ceasar, c\n\t2, homophonic, h\n\t3, polyalphabetic, p\n\t4, numeric, n\n\t5, affine, a", buffer).into())
+}
+
+/// Pobiera od użytkownika informację o języku tekstu
+fn prompt_language() -> Result<Language, Box<dyn Error>> {
+    println!("Choose language\n1. english\n2. polish");
+    let mut buffer: String = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    match buffer.trim() {
+        "1" => Ok(Language::English),
+        "2" => Ok(Language::Polish),
+        other => Language::parse(other)
+                    .ok_or_else(|| format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, english, en\n\t2, polish, pl", buffer).into())
+    }
 }
 
 /// Pobiera od użytkownika informację o kluczu
@@ -92,39 +741,134 @@ fn prompt_keycode() -> Result<Key, Box<dyn Error>> {
     let mut data: Vec<String> = vec![String::new(); 2];
     println!("Pass full path to key:");
     stdin.read_line(&mut data[0])?;
-    println!("How to handle your key?\n1. column\n2. row");
+    println!("How to handle your key?\n1. column\n2. row\n3. labeled");
     stdin.read_line(&mut data[1])?;
-    let rule: KeyRule = match data[1].trim() {
-        "1" | "column" | "c" => KeyRule::Column,
-        "2" | "row" | "r" => KeyRule::Row,
-        _ => return Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, column, c\n\t2, row, r", data[1]).into())
+    let rule: KeyRule = match parse_key_rule(&data[1]) {
+        Some(rule) => rule,
+        None => return Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, column, c\n\t2, row, r\n\t3, labeled, l", data[1]).into())
     };
-    Key::read(data[0].trim(), rule)
+    Ok(Key::read(data[0].trim(), rule)?)
+}
+
+/// Pobiera od użytkownika słowo kluczowe
+fn prompt_keyword() -> Result<Key, Box<dyn Error>> {
+    println!("Pass keyword:");
+    let mut buffer: String = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    Ok(Key::from_keyword(&buffer))
 }
 
 /// Pobiera od użytkownika informację o przesunięciu (szyfr cezara)
-fn prompt_ceasar_data() -> Result<Box<dyn Cipher>, Box<dyn Error>> {
-    println!("Pass offset:");
+fn prompt_ceasar_data(language: Language) -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+    println!("Pass offset (number or letter):");
     let mut buffer: String = String::new();
     io::stdin().read_line(&mut buffer)?;
-    let offset: u8 = buffer.trim().parse()?;
-    Ok(Box::new(CeasarData::new(offset))) 
+    Ok(Box::new(parse_ceasar(&buffer, language.alphabet())?)) 
+}
+
+/// Pobiera od użytkownika informację o mnożniku i przesunięciu (szyfr afiniczny)
+fn prompt_affine_data(language: Language) -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+    println!("Pass multiplier and offset (e.g. 5 8):");
+    let mut buffer: String = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    let numbers: Vec<i64> = buffer.split_whitespace()
+                                  .map(|value: &str| value.parse().map_err(|_| format!("INVALID NUMBER: {}", value)))
+                                  .collect::<Result<Vec<i64>, String>>()?;
+    match numbers[..] {
+        [multiplier, offset] => Ok(Box::new(AffineData::with_alphabet(multiplier, offset, language.alphabet())?)),
+        _ => Err(format!("INVALID INPUT: {}\nPASS TWO NUMBERS SEPARATED BY SPACE", buffer.trim()).into())
+    }
 }
 
 /// Pobiera od użytkownika informację o kluczu (szyfr homofoniczny)
-fn prompt_homophonic_data() -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+fn prompt_homophonic_data(language: Language) -> Result<Box<dyn Cipher>, Box<dyn Error>> {
     let code: Key = prompt_keycode()?;
-    let algorithm: HomophonicData = HomophonicData::new(code)?;
-    Ok(Box::new(algorithm)) 
+    // klucz z symbolami tekstu jawnego bądź z alfabetem w nagłówku sam określa alfabet (jak w trybie z opcjami)
+    let algorithm: HomophonicData = match code.rule == KeyRule::Labeled || code.alphabet().is_some() {
+        true => HomophonicData::new(code)?,
+        false => HomophonicData::with_alphabet(code, language.mixed_alphabet())?
+    };
+    println!("Pass full path to codebook (leave empty to use homophones only):");
+    let mut path: String = String::new();
+    io::stdin().read_line(&mut path)?;
+    match path.trim() {
+        "" => Ok(Box::new(algorithm)),
+        path => Ok(Box::new(NomenclatorData::new(algorithm, Codebook::read(path)?)?))
+    }
 }
 
 /// Pobiera od użytkownika informację o kluczu (szyfr wieloalfabetowy)
-fn prompt_polyalphabetic_data() -> Result<Box<dyn Cipher>, Box<dyn Error>> {
-    let code: Key = prompt_keycode()?;
-    let algorithm: PolyalphabeticData = PolyalphabeticData::new(code)?;
+fn prompt_polyalphabetic_data(language: Language) -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+    println!("Choose key source\n1. file\n2. keyword");
+    let mut buffer: String = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    let (code, alphabet): (Key, Alphabet) = match (buffer.trim().to_lowercase().as_str(), language) {
+        ("1" | "file" | "f", Language::English) => (prompt_keycode()?, Alphabet::latin_mixed()),
+        ("1" | "file" | "f", _) => (prompt_keycode()?, language.alphabet()),
+        ("2" | "keyword" | "k", _) => (prompt_keyword()?, language.alphabet()),
+        _ => return Err(format!("INVALID OPTION: {}\nVALID OPTIONS ARE: \n\t1, file, f\n\t2, keyword, k", buffer).into())
+    };
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, alphabet)?;
     Ok(Box::new(algorithm))
 }
 
+/// Pobiera od użytkownika informację o kluczu (szyfr homofoniczny z kodami liczbowymi)
+fn prompt_numeric_data() -> Result<Box<dyn Cipher>, Box<dyn Error>> {
+    println!("Pass full path to numeric key:");
+    let mut buffer: String = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    Ok(Box::new(NumericHomophonicData::new(NumericKey::read(buffer.trim())?)?))
+}
+
+/// Generuje klucz dla wybranego szyfru i zapisuje go we wskazanym przez użytkownika pliku
+fn prompt_keygen(cipher: CipherName, language: Language) -> Result<(), Box<dyn Error>> {
+    let stdin: Stdin = io::stdin();
+    let key: Key = match cipher {
+        CipherName::Ceasar => {
+            println!("Ceasar cipher does not use key file, pass offset when encrypting instead");
+            return Ok(());
+        },
+        CipherName::Affine => {
+            println!("Affine cipher does not use key file, pass multiplier and offset when encrypting instead");
+            return Ok(());
+        },
+        CipherName::Homophonic => Key::generate_homophonic(language, ('!'..='~').collect())?.header(KeyHeader::new().cipher("homophonic")),
+        CipherName::Polyalphabetic => {
+            println!("Pass key length:");
+            let mut buffer: String = String::new();
+            stdin.read_line(&mut buffer)?;
+            let length: usize = buffer.trim()
+                                      .parse()
+                                      .map_err(|_| format!("INVALID NUMBER: {}", buffer.trim()))?;
+            let alphabet: Alphabet = match language {
+                Language::English => Alphabet::latin_mixed(),
+                _ => language.alphabet()
+            };
+            PolyalphabeticData::generate_key(&PolyalphabeticKeyParams { alphabet, length, substitution: false })?
+        },
+        CipherName::Numeric => {
+            let key: NumericKey = NumericHomophonicData::generate_key(&NumericKeyParams::for_language(language, 2))?;
+            return prompt_save_key(&key.to_string(), |path: &str| key.write(path));
+        }
+    };
+    prompt_save_key(&key.to_file_string(), |path: &str| key.write(path))
+}
+
+/// Zapisuje klucz we wskazanym przez użytkownika pliku bądź wyświetla go
+fn prompt_save_key(key: &str, write: impl Fn(&str) -> Result<(), CipherError>) -> Result<(), Box<dyn Error>> {
+    println!("Pass full path to save key (leave empty to print it):");
+    let mut path: String = String::new();
+    io::stdin().read_line(&mut path)?;
+    match path.trim() {
+        "" => println!("{}", key),
+        path => {
+            write(path)?;
+            println!("Key saved to {}", path);
+        }
+    }
+    Ok(())
+}
+
 /// Pobiera od użytkownika informację o ciągu znaków
 fn prompt_data() -> Result<String, Box<dyn Error>> {
     println!("Pass text to cipher:");
@@ -134,7 +878,7 @@ fn prompt_data() -> Result<String, Box<dyn Error>> {
 }
 
 /// Powtarza podaną funkcję w nieskończoność w przypadku kiedy zwróci ona błąd
-fn loop_error<T>(repeat: fn() -> Result<T, Box<dyn Error>>) -> T {
+fn loop_error<T>(repeat: impl Fn() -> Result<T, Box<dyn Error>>) -> T {
     let mut output: Result<T, Box<dyn Error>> = repeat();
     while let Err(error) = output {
         eprintln!("{}", error);
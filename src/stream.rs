@@ -0,0 +1,145 @@
+use std::io::{ErrorKind, Read, Write};
+use std::str;
+use crate::algorithms::{ByteCipher, Cipher, Position};
+use crate::Error;
+
+/// Rozmiar fragmentu odczytywanego ze strumienia (w bajtach)
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Szyfruje dane ze strumienia fragmentami, zapisując wynik do drugiego strumienia
+/// 
+/// # Arguments
+/// 
+/// * `cipher` - szyfr do wykorzystania
+/// * `reader` - źródło tekstu jawnego (UTF-8)
+/// * `writer` - miejsce zapisu szyfrogramu
+/// 
+/// # Errors
+/// 
+/// * [`Error::Io`] - błędy odczytu bądź zapisu strumienia
+/// * [`Error::InvalidUtf8`] - dane nie są poprawnym tekstem UTF-8
+/// * [`Error::UnknownSymbol`] - tekst zawiera znak, którego nie można zaszyfrować (patrz [`Cipher::try_encrypt`])
+pub fn encrypt_stream(cipher: &dyn Cipher, reader: impl Read, writer: impl Write) -> Result<(), Error> {
+    let mut position: Position = Position::default();
+    process(reader, writer, |data: &str| cipher.try_encrypt_at(data, &mut position))
+}
+
+/// Odszyfrowuje dane ze strumienia fragmentami, zapisując wynik do drugiego strumienia
+/// 
+/// # Arguments
+/// 
+/// * `cipher` - szyfr do wykorzystania
+/// * `reader` - źródło szyfrogramu (UTF-8)
+/// * `writer` - miejsce zapisu tekstu jawnego
+/// 
+/// # Errors
+/// 
+/// * [`Error::Io`] - błędy odczytu bądź zapisu strumienia
+/// * [`Error::InvalidUtf8`] - dane nie są poprawnym tekstem UTF-8
+/// * [`Error::UnknownSymbol`] - szyfrogram zawiera znak spoza klucza (patrz [`Cipher::try_decrypt`])
+pub fn decrypt_stream(cipher: &dyn Cipher, reader: impl Read, writer: impl Write) -> Result<(), Error> {
+    let mut position: Position = Position::default();
+    process(reader, writer, |data: &str| cipher.try_decrypt_at(data, &mut position))
+}
+
+/// Szyfruje dowolne dane binarne ze strumienia fragmentami, zapisując wynik do drugiego strumienia
+/// 
+/// # Arguments
+/// 
+/// * `cipher` - szyfr do wykorzystania
+/// * `reader` - źródło danych
+/// * `writer` - miejsce zapisu szyfrogramu
+/// 
+/// # Errors
+/// 
+/// [`Error::Io`] - błędy odczytu bądź zapisu strumienia
+pub fn encrypt_bytes_stream(cipher: &dyn ByteCipher, reader: impl Read, writer: impl Write) -> Result<(), Error> {
+    process_bytes(reader, writer, |byte: u8, position: usize| cipher.encrypt_byte(byte, position))
+}
+
+/// Odszyfrowuje dowolne dane binarne ze strumienia fragmentami, zapisując wynik do drugiego strumienia
+/// 
+/// # Arguments
+/// 
+/// * `cipher` - szyfr do wykorzystania
+/// * `reader` - źródło szyfrogramu
+/// * `writer` - miejsce zapisu danych
+/// 
+/// # Errors
+/// 
+/// [`Error::Io`] - błędy odczytu bądź zapisu strumienia
+pub fn decrypt_bytes_stream(cipher: &dyn ByteCipher, reader: impl Read, writer: impl Write) -> Result<(), Error> {
+    process_bytes(reader, writer, |byte: u8, position: usize| cipher.decrypt_byte(byte, position))
+}
+
+/// Odczytuje strumień fragmentami, przekształcając każdy bajt z uwzględnieniem jego pozycji
+fn process_bytes(mut reader: impl Read, mut writer: impl Write,
+                 transform: impl Fn(u8, usize) -> u8) -> Result<(), Error> {
+    let mut buffer: Vec<u8> = vec![0; CHUNK_SIZE];
+    let mut offset: usize = 0;
+    loop {
+        let read: usize = match reader.read(&mut buffer) {
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into())
+        };
+        if read == 0 {
+            break;
+        }
+        buffer[..read].iter_mut()
+                      .enumerate()
+                      .for_each(|(index, byte): (usize, &mut u8)| *byte = transform(*byte, offset + index));
+        writer.write_all(&buffer[..read])?;
+        offset += read;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Odczytuje strumień fragmentami, nie rozdzielając znaków UTF-8 (ani, o ile to możliwe, linijek) pomiędzy fragmenty
+fn process(mut reader: impl Read, mut writer: impl Write, 
+           mut transform: impl FnMut(&str) -> Result<String, Error>) -> Result<(), Error> {
+    let mut buffer: Vec<u8> = vec![0; CHUNK_SIZE];
+    let mut pending: Vec<u8> = Vec::new();
+    let mut offset: usize = 0;
+    loop {
+        let read: usize = match reader.read(&mut buffer) {
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into())
+        };
+        if read == 0 {
+            break;
+        }
+        pending.extend_from_slice(&buffer[..read]);
+
+        // niepełny znak na końcu fragmentu czeka na kolejny odczyt
+        let valid: usize = match str::from_utf8(&pending) {
+            Ok(_) => pending.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => return Err(Error::InvalidUtf8 { position: offset + error.valid_up_to() })
+        };
+        let data: &str = str::from_utf8(&pending[..valid]).expect("Checked above");
+        // fragment kończy się na ostatnim końcu linii, by nie rozdzielać słów i wyrażeń (np. słów i grup kodowych
+        // nomenklatora), a gdy w buforze zebrał się pełny fragment bez końca linii - przed ostatnim ciągiem białych znaków
+        // (kolejny fragment zaczyna się od białego znaku, np. separatora kodów liczbowych)
+        let end: usize = match data.rfind('\n') {
+            Some(index) => index + 1,
+            None if valid < CHUNK_SIZE => 0,
+            None => data.char_indices()
+                        .map(|(index, c): (usize, char)| (index + c.len_utf8(), c))
+                        .rfind(|&(end, c): &(usize, char)| !c.is_whitespace() && data[end..].starts_with(char::is_whitespace))
+                        .map_or(valid, |(end, _): (usize, char)| end)
+        };
+        writer.write_all(transform(&data[..end])?.as_bytes())?;
+        pending.drain(..end);
+        offset += end;
+    }
+    match str::from_utf8(&pending) {
+        Ok(data) if !data.is_empty() => writer.write_all(transform(data)?.as_bytes())?,
+        Ok(_) => {},
+        Err(error) => return Err(Error::InvalidUtf8 { position: offset + error.valid_up_to() })
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -1,16 +1,58 @@
-use std::{fs, error::Error};
-use rand::{Rng, prelude::{SliceRandom, ThreadRng}};
+use std::{env, fs, fmt, io};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{Alphabet, Error, Language};
+use rand::{Rng, prelude::SliceRandom};
+use std::collections::{HashMap, HashSet};
 use core::cmp;
 
+/// Zmienna środowiskowa z listą katalogów kluczy i alfabetów (rozdzielonych jak w zmiennej `PATH`)
+pub const KEYS_PATH_VAR: &str = "SZYFRY_KEYS";
+
+/// Katalog kluczy i alfabetów wykorzystywany, gdy zmienna [`KEYS_PATH_VAR`] nie jest ustawiona
+pub const DEFAULT_KEYS_DIR: &str = "translation_keys";
+
+/// Zwraca katalogi, w których wyszukiwane są klucze i alfabety
+/// (ze zmiennej [`KEYS_PATH_VAR`] bądź [`DEFAULT_KEYS_DIR`]; zawsze co najmniej jeden)
+pub fn search_path() -> Vec<PathBuf> {
+    let dirs: Vec<PathBuf> = env::var_os(KEYS_PATH_VAR)
+        .map(|value: std::ffi::OsString| env::split_paths(&value).filter(|p: &PathBuf| !p.as_os_str().is_empty()).collect())
+        .unwrap_or_default();
+    match dirs.is_empty() {
+        true => vec![PathBuf::from(DEFAULT_KEYS_DIR)],
+        false => dirs
+    }
+}
+
+/// Zwraca ścieżkę pliku o podanej nazwie w pierwszym katalogu kluczy, który go zawiera
+/// (bądź w pierwszym katalogu, gdy plik nie istnieje w żadnym z nich)
+/// 
+/// # Arguments
+/// 
+/// * `name` - nazwa pliku względem katalogu kluczy
+pub fn translation_path(name: &str) -> String {
+    let dirs: Vec<PathBuf> = search_path();
+    let path: PathBuf = dirs.iter()
+                            .map(|dir: &PathBuf| dir.join(name))
+                            .find(|path: &PathBuf| path.is_file())
+                            .unwrap_or_else(|| dirs[0].join(name));
+    path.to_string_lossy().into_owned()
+}
+
 /// Do określania w jaki sposób odczytywać pliki
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KeyRule {
     /// Linijka po linijce
     Row,
     /// Kolumna po kolumnie
     Column,
+    /// Linijka po linijce, każda linijka poprzedzona symbolem tekstu jawnego (np. `A: xQ7`)
+    Labeled,
 }
 
 /// Do przechowywania klucza szyfrującego
+#[derive(Debug, Clone)]
 pub struct Key
 {
     /// Reprzentacja klucza jako wektor (w zależności od sposobu odczytywania)
@@ -18,56 +60,544 @@ pub struct Key
     /// Sposób reprezentacji klucza
     pub rule: KeyRule,
     /// Czy klucz zawiera unikalne znaki
-    pub unique: bool
+    pub unique: bool,
+    /// Nagłówek z metadanymi (brak dla kluczy w starym formacie)
+    pub(crate) header: Option<KeyHeader>,
+    /// Symbole tekstu jawnego odpowiadające kolejnym pozycjom (tylko dla [`KeyRule::Labeled`], w innym przypadku puste)
+    pub(crate) labels: Vec<char>,
+    /// Miejsca znaków kolejnych pozycji w odczytanym tekście klucza (tylko dla kluczy z [`Key::parse`], w innym przypadku puste)
+    pub(crate) locations: Vec<Vec<Location>>
+}
+
+/// Pierwsza linijka pliku klucza z nagłówkiem (bez numeru wersji)
+const HEADER_MAGIC: &str = "#szyfry-key v";
+
+/// Linijka kończąca nagłówek pliku klucza
+const HEADER_END: &str = "---";
+
+/// Aktualna wersja formatu pliku klucza
+pub const KEY_FORMAT_VERSION: u32 = 1;
+
+/// Metadane zapisywane na początku pliku klucza
+/// 
+/// ```text
+/// #szyfry-key v1
+/// rule: column
+/// cipher: homophonic
+/// alphabet: latin-mixed
+/// created: 1700000000
+/// nulls: ~^|
+/// checksum: 8f1c0b7a5e3d2c10
+/// ---
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHeader {
+    /// Wersja formatu pliku
+    pub version: u32,
+    /// Sposób zapisu klucza
+    pub rule: Option<KeyRule>,
+    /// Szyfr, dla którego przeznaczony jest klucz
+    pub cipher: Option<String>,
+    /// Alfabet tekstu jawnego (zapisywany jako nazwa wbudowanego alfabetu bądź symbole w cudzysłowie)
+    pub alphabet: Option<Alphabet>,
+    /// Czas utworzenia klucza (sekundy od 1970-01-01 UTC)
+    pub created: Option<u64>,
+    /// Suma kontrolna pozycji klucza (patrz [`Key::checksum`])
+    pub checksum: Option<u64>,
+    /// Symbole puste (nieznaczące), wstawiane do szyfrogramu i usuwane przy odszyfrowywaniu
+    pub nulls: Vec<char>
+}
+
+/// Metody nagłówka klucza
+impl KeyHeader {
+    /// Tworzy nagłówek w aktualnej wersji formatu z bieżącym czasem utworzenia
+    pub fn new() -> KeyHeader {
+        let created: Option<u64> = SystemTime::now().duration_since(UNIX_EPOCH)
+                                                    .ok()
+                                                    .map(|d: std::time::Duration| d.as_secs());
+        KeyHeader { version: KEY_FORMAT_VERSION, rule: None, cipher: None, alphabet: None, created, checksum: None, nulls: Vec::new() }
+    }
+
+    /// Ustawia szyfr, dla którego przeznaczony jest klucz
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cipher` - nazwa szyfru
+    pub fn cipher(mut self, cipher: &str) -> KeyHeader {
+        self.cipher = Some(cipher.to_string());
+        self
+    }
+
+    /// Ustawia alfabet tekstu jawnego
+    /// 
+    /// # Arguments
+    /// 
+    /// * `alphabet` - alfabet tekstu jawnego
+    pub fn alphabet(mut self, alphabet: &Alphabet) -> KeyHeader {
+        self.alphabet = Some(alphabet.clone());
+        self
+    }
+
+    /// Ustawia czas utworzenia klucza (`None` - bez czasu utworzenia, np. dla powtarzalnych kluczy)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `created` - sekundy od 1970-01-01 UTC
+    pub fn created(mut self, created: Option<u64>) -> KeyHeader {
+        self.created = created;
+        self
+    }
+
+    /// Ustawia symbole puste
+    /// 
+    /// # Arguments
+    /// 
+    /// * `nulls` - symbole puste
+    pub fn nulls(mut self, nulls: Vec<char>) -> KeyHeader {
+        self.nulls = nulls;
+        self
+    }
+
+    /// Odczytuje nagłówek z początku tekstu i zwraca go wraz z pozostałą częścią tekstu
+    /// (`None` dla tekstu w starym formacie bez nagłówka)
+    fn parse(str: &str) -> Result<Option<(KeyHeader, &str)>, Error> {
+        let first: &str = str.lines().next().unwrap_or_default();
+        let version: &str = match first.trim_end().strip_prefix(HEADER_MAGIC) {
+            Some(version) => version,
+            None => return Ok(None)
+        };
+        let version: u32 = version.parse().map_err(|_| Error::InvalidKeyHeader { line: 1 })?;
+        if version != KEY_FORMAT_VERSION {
+            return Err(Error::UnsupportedKeyVersion { version });
+        }
+
+        let mut header: KeyHeader = KeyHeader { version, rule: None, cipher: None, alphabet: None, created: None, checksum: None, nulls: Vec::new() };
+        let mut offset: usize = 0;
+        for (index, line) in str.split_inclusive('\n').enumerate() {
+            offset += line.len();
+            let line: &str = line.trim();
+            if index == 0 {
+                continue;
+            } else if line == HEADER_END {
+                return Ok(Some((header, &str[offset..])));
+            }
+            let invalid = || Error::InvalidKeyHeader { line: index + 1 };
+            let (name, value): (&str, &str) = line.split_once(':').ok_or_else(invalid)?;
+            let value: &str = value.trim();
+            match name.trim() {
+                "rule" => header.rule = Some(match value {
+                    "row" => KeyRule::Row,
+                    "column" => KeyRule::Column,
+                    "labeled" => KeyRule::Labeled,
+                    _ => return Err(invalid())
+                }),
+                "cipher" => header.cipher = Some(value.to_string()),
+                "alphabet" => {
+                    // symbole w cudzysłowie, nazwa wbudowanego alfabetu bądź (starszy zapis) symbole bez cudzysłowu
+                    let alphabet: Option<Alphabet> = match value.starts_with('"') {
+                        true => unquote(value).and_then(|symbols: Vec<char>| Alphabet::new(symbols).ok()),
+                        false => Alphabet::preset(value).or_else(|| Alphabet::new(value.chars().filter(|c: &char| !c.is_whitespace()).collect()).ok())
+                    };
+                    header.alphabet = Some(alphabet.ok_or_else(invalid)?);
+                },
+                "created" => header.created = Some(value.parse().map_err(|_| invalid())?),
+                "checksum" => header.checksum = Some(u64::from_str_radix(value, 16).map_err(|_| invalid())?),
+                "nulls" => {
+                    let mut seen: HashSet<char> = HashSet::new();
+                    header.nulls = value.chars().filter(|c: &char| !c.is_whitespace()).collect();
+                    if !header.nulls.iter().all(|&c: &char| seen.insert(c)) {
+                        return Err(invalid());
+                    }
+                },
+                // nieznane pola są pomijane (zgodność z nowszymi wersjami w obrębie tej samej wersji formatu)
+                _ => {}
+            }
+        }
+        Err(Error::InvalidKeyHeader { line: str.lines().count() + 1 })
+    }
+}
+
+/// Domyślny nagłówek (patrz [`KeyHeader::new`])
+impl Default for KeyHeader {
+    fn default() -> KeyHeader {
+        KeyHeader::new()
+    }
+}
+
+/// Zapis nagłówka (wraz z linijką kończącą)
+impl fmt::Display for KeyHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}", HEADER_MAGIC, self.version)?;
+        if let Some(rule) = self.rule {
+            writeln!(f, "rule: {}", match rule {
+                KeyRule::Row => "row",
+                KeyRule::Column => "column",
+                KeyRule::Labeled => "labeled"
+            })?;
+        }
+        if let Some(cipher) = &self.cipher {
+            writeln!(f, "cipher: {}", cipher)?;
+        }
+        if let Some(alphabet) = &self.alphabet {
+            match alphabet.name() {
+                Some(name) => writeln!(f, "alphabet: {}", name)?,
+                None => writeln!(f, "alphabet: {}", quote(alphabet.symbols()))?
+            }
+        }
+        if let Some(created) = self.created {
+            writeln!(f, "created: {}", created)?;
+        }
+        if !self.nulls.is_empty() {
+            writeln!(f, "nulls: {}", self.nulls.iter().collect::<String>())?;
+        }
+        if let Some(checksum) = self.checksum {
+            writeln!(f, "checksum: {:016x}", checksum)?;
+        }
+        writeln!(f, "{}", HEADER_END)
+    }
+}
+
+/// Zapisuje symbole w cudzysłowie (`"`, `\` oraz tabulacja i końce linii poprzedzone są `\`)
+fn quote(symbols: &[char]) -> String {
+    let mut output: String = String::from('"');
+    for &symbol in symbols {
+        match symbol {
+            '"' | '\\' => { output.push('\\'); output.push(symbol); },
+            '\t' => output.push_str("\\t"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            _ => output.push(symbol)
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Odczytuje symbole zapisane w cudzysłowie (patrz [`quote`]), `None` dla niepoprawnego zapisu
+fn unquote(value: &str) -> Option<Vec<char>> {
+    let mut chars: std::str::Chars = value.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut symbols: Vec<char> = Vec::new();
+    while let Some(symbol) = chars.next() {
+        symbols.push(match symbol {
+            '\\' => match chars.next()? {
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                escaped @ ('"' | '\\') => escaped,
+                _ => return None
+            },
+            '"' => return None,
+            _ => symbol
+        });
+    }
+    Some(symbols)
+}
+
+/// Miejsce znaku w odczytanym pliku klucza (wraz z nagłówkiem) bądź, dla kluczy utworzonych w inny sposób,
+/// w tekstowej reprezentacji klucza (patrz [`fmt::Display`] dla [`Key`]), liczone od 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Numer linijki
+    pub row: usize,
+    /// Numer kolumny (w znakach)
+    pub column: usize
+}
+
+/// Znak występujący w kluczu więcej niż raz
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    /// Powtórzony znak
+    pub character: char,
+    /// Wszystkie miejsca wystąpienia znaku
+    pub locations: Vec<Location>
+}
+
+/// Wynik walidacji klucza
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyReport {
+    /// Powtórzone znaki (w kolejności pierwszego powtórzenia)
+    pub duplicates: Vec<Duplicate>,
+    /// Symbole puste z nagłówka wykorzystane również jako homofony (wraz z miejscami wystąpienia w kluczu)
+    pub colliding_nulls: Vec<Duplicate>
+}
+
+/// Metody raportu walidacji klucza
+impl KeyReport {
+    /// Czy klucz nie zawiera błędów
+    pub fn is_valid(&self) -> bool {
+        self.duplicates.is_empty() && self.colliding_nulls.is_empty()
+    }
 }
 
 /// Metody klucza
 impl Key {
     /// Zczytuje dane z pliku i na ich podstawie tworzy instancje klucza
     /// 
+    /// Sposób odczytu brany jest kolejno z nagłówka pliku, argumentu `rule`, rozszerzenia pliku
+    /// (`.ckey` - kolumny, `.lkey` - linijki, `.hkey` - linijki z symbolami), a w ostateczności czytane są linijki
+    /// 
     /// # Arguments
     /// 
     /// * `path` - ścieżka odczytu
-    /// * `rule` - sposób odczytu klucza
+    /// * `rule` - sposób odczytu klucza bez nagłówka (`None` - wykrycie na podstawie rozszerzenia)
     /// 
     /// # Errors
     /// 
-    /// Błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
-    pub fn read(path: &str, rule: KeyRule) -> Result<Key, Box<dyn Error>> {
-        let str: String = fs::read_to_string(path)?;
+    /// * [`Error::KeyIo`] - błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
+    /// * błędy nagłówka (patrz [`Key::parse`])
+    pub fn read(path: &str, rule: impl Into<Option<KeyRule>>) -> Result<Key, Error> {
+        let str: String = fs::read_to_string(path)
+            .map_err(|source: io::Error| Error::KeyIo { path: path.to_string(), source })?;
+        let extension: Option<KeyRule> = match Path::new(path).extension().and_then(|e: &std::ffi::OsStr| e.to_str()) {
+            Some("ckey") => Some(KeyRule::Column),
+            Some("hkey") => Some(KeyRule::Labeled),
+            Some("lkey") => Some(KeyRule::Row),
+            _ => None
+        };
+        Key::parse(&str, rule.into().or(extension))
+    }
 
-        // zczytaj dane w zależności od podanej zasady
+    /// Zczytuje klucz z dowolnego źródła danych (np. standardowego wejścia)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reader` - źródło danych
+    /// * `rule` - sposób odczytu klucza bez nagłówka (`None` - linijka po linijce)
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::Io`] - błędy odczytu bądź dane niebędące poprawnym tekstem UTF-8
+    /// * błędy nagłówka (patrz [`Key::parse`])
+    pub fn from_reader(mut reader: impl Read, rule: impl Into<Option<KeyRule>>) -> Result<Key, Error> {
+        let mut str: String = String::new();
+        reader.read_to_string(&mut str)?;
+        Key::parse(&str, rule)
+    }
+
+    /// Tworzy klucz z tekstu w formacie pliku klucza (z nagłówkiem bądź bez)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `str` - zawartość klucza
+    /// * `rule` - sposób odczytu klucza bez nagłówka bądź z nagłówkiem bez pola `rule` (`None` - linijka po linijce)
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::InvalidKeyHeader`] - niepoprawna linijka nagłówka
+    /// * [`Error::UnsupportedKeyVersion`] - nieobsługiwana wersja formatu
+    /// * [`Error::ChecksumMismatch`] - suma kontrolna z nagłówka nie zgadza się z kluczem
+    /// * [`Error::MalformedKeyLine`] - linijka klucza [`KeyRule::Labeled`] nie zaczyna się od symbolu i dwukropka
+    /// * [`Error::DuplicateSymbol`] - symbol tekstu jawnego powtarza się w kluczu [`KeyRule::Labeled`]
+    pub fn parse(str: &str, rule: impl Into<Option<KeyRule>>) -> Result<Key, Error> {
+        let (header, body): (Option<KeyHeader>, &str) = match KeyHeader::parse(str)? {
+            Some((header, body)) => (Some(header), body),
+            None => (None, str)
+        };
+        let rule: KeyRule = header.as_ref()
+                                  .and_then(|h: &KeyHeader| h.rule)
+                                  .or(rule.into())
+                                  .unwrap_or(KeyRule::Row);
+
+        // zczytaj dane w zależności od podanej zasady, zapamiętując miejsca znaków w pliku
+        let skipped: usize = str.lines().count() - body.lines().count();
         let mut data: Vec<String> = Vec::new();
+        let mut labels: Vec<char> = Vec::new();
+        let mut locations: Vec<Vec<Location>> = Vec::new();
         match rule {
             KeyRule::Row => {
-                data.resize(str.lines().count(), String::new());
-                str .lines()
-                    .zip(&mut data)
-                    .for_each(|(l, v): (&str, &mut String)| 
-                        v.push_str(&l.split_whitespace()
-                                             .collect::<String>())
-                    );
+                for (index, l) in body.lines().enumerate() {
+                    let signs: Vec<(usize, char)> = l.chars()
+                                                     .enumerate()
+                                                     .filter(|(_, c): &(usize, char)| !c.is_whitespace())
+                                                     .collect();
+                    data.push(signs.iter().map(|&(_, c): &(usize, char)| c).collect());
+                    locations.push(signs.iter()
+                                        .map(|&(column, _): &(usize, char)| Location { row: skipped + index + 1, column: column + 1 })
+                                        .collect());
+                }
             },
             KeyRule::Column => {
-                let cap: usize = str.lines()
-                                    .max_by_key(|&l: &&str| l.split_whitespace()
-                                                            .collect::<String>()
-                                                            .len())
-                                    .unwrap()
-                                    .len();
+                let cap: usize = body.lines()
+                                     .max_by_key(|&l: &&str| l.split_whitespace()
+                                                             .collect::<String>()
+                                                             .chars()
+                                                             .count())
+                                     .map_or(0, |l: &str| l.chars().count());
                 data.resize(cap, String::new());
-                str .lines()
-                    .for_each(|l: &str| l.chars()
-                                         .zip(&mut data)
-                                         .filter(|(l, _d): &(char, &mut String)| !l.is_whitespace())
-                                         .for_each(|(c, s): (char, &mut String)| s.push(c)));
+                locations.resize(cap, Vec::new());
+                for (index, l) in body.lines().enumerate() {
+                    l.chars()
+                     .enumerate()
+                     .take(cap)
+                     .filter(|(_, c): &(usize, char)| !c.is_whitespace())
+                     .for_each(|(column, c): (usize, char)| {
+                         data[column].push(c);
+                         locations[column].push(Location { row: skipped + index + 1, column: column + 1 });
+                     });
+                }
+            },
+            KeyRule::Labeled => {
+                for (index, l) in body.lines().enumerate() {
+                    if l.trim().is_empty() {
+                        continue;
+                    }
+                    let indent: usize = l.chars().count() - l.trim_start().chars().count();
+                    let mut chars: std::str::Chars = l.trim_start().chars();
+                    let (label, separator): (Option<char>, Option<char>) = (chars.next(), chars.next());
+                    let label: char = match (label, separator) {
+                        (Some(label), Some(':')) => label,
+                        _ => return Err(Error::MalformedKeyLine { line: skipped + index + 1 })
+                    };
+                    if labels.contains(&label) {
+                        return Err(Error::DuplicateSymbol { character: label });
+                    }
+                    // znaki po wcięciu, symbolu tekstu jawnego i dwukropku
+                    let signs: Vec<(usize, char)> = chars.enumerate()
+                                                         .filter(|(_, c): &(usize, char)| !c.is_whitespace())
+                                                         .collect();
+                    labels.push(label);
+                    data.push(signs.iter().map(|&(_, c): &(usize, char)| c).collect());
+                    locations.push(signs.iter()
+                                        .map(|&(column, _): &(usize, char)| Location { row: skipped + index + 1, column: indent + column + 3 })
+                                        .collect());
+                }
             }
         }
-        let unique: bool = !(1..data.len()).any(|i| data[i..].contains(&data[i - 1]));        
-        Ok(Key { data, rule, unique })
+        let key: Key = Key { header, labels, locations, ..Key::new(data, rule) };
+        if let Some(expected) = key.header.as_ref().and_then(|h: &KeyHeader| h.checksum) {
+            let found: u64 = key.checksum();
+            if expected != found {
+                return Err(Error::ChecksumMismatch { expected, found });
+            }
+        }
+        Ok(key)
+    }
+
+    /// Ustawia nagłówek zapisywany razem z kluczem
+    /// 
+    /// # Arguments
+    /// 
+    /// * `header` - nagłówek klucza
+    pub fn header(mut self, header: KeyHeader) -> Key {
+        self.header = Some(header);
+        self
+    }
+
+    /// Nagłówek z metadanymi (brak dla kluczy w starym formacie)
+    pub fn metadata(&self) -> Option<&KeyHeader> {
+        self.header.as_ref()
+    }
+
+    /// Symbole tekstu jawnego odpowiadające kolejnym pozycjom (tylko dla [`KeyRule::Labeled`], w innym przypadku puste)
+    pub fn labels(&self) -> &[char] {
+        &self.labels
+    }
+
+    /// Zmienia sposób zapisu klucza na [`KeyRule::Row`] lub [`KeyRule::Column`] (symbole tekstu jawnego są pomijane)
+    /// 
+    /// Do zamiany na [`KeyRule::Labeled`] służy [`Key::label`], dla którego ta metoda nie zmienia klucza
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rule` - nowy sposób reprezentacji klucza
+    pub fn rule(mut self, rule: KeyRule) -> Key {
+        if rule != KeyRule::Labeled {
+            self.labels.clear();
+            self.locations.clear();
+            self.rule = rule;
+        }
+        self
+    }
+
+    /// Suma kontrolna pozycji klucza wraz z symbolami tekstu jawnego i symbolami pustymi
+    /// (FNV-1a, niezależna od sposobu zapisu)
+    pub fn checksum(&self) -> u64 {
+        let labels: String = self.labels.iter().map(|l: &char| format!("{}:", l)).collect();
+        let nulls: String = match self.nulls() {
+            [] => String::new(),
+            nulls => format!("\nnulls:{}", nulls.iter().collect::<String>())
+        };
+        (labels + &self.data.join("\n") + &nulls)
+                 .bytes()
+                 .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte: u8| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+    }
+
+    /// Zwraca zawartość pliku klucza: nagłówek (jeżeli jest ustawiony, z aktualnym sposobem zapisu i sumą kontrolną) oraz klucz
+    pub fn to_file_string(&self) -> String {
+        match &self.header {
+            Some(header) => {
+                let header: KeyHeader = KeyHeader { rule: Some(self.rule), checksum: Some(self.checksum()), ..header.clone() };
+                format!("{}{}", header, self)
+            },
+            None => self.to_string()
+        }
+    }
+
+    /// Tworzy klucz z podanych pozycji
+    /// 
+    /// # Arguments
+    /// 
+    /// * `data` - kolejne pozycje klucza
+    /// * `rule` - sposób reprezentacji klucza
+    pub fn new(data: Vec<String>, rule: KeyRule) -> Key {
+        let unique: bool = is_unique(&data);
+        Key { data, rule, unique, header: None, labels: Vec::new(), locations: Vec::new() }
+    }
+
+    /// Tworzy klucz, w którym każda pozycja przypisana jest do symbolu tekstu jawnego ([`KeyRule::Labeled`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - symbole tekstu jawnego
+    /// * `data` - homofony kolejnych symboli
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::DuplicateSymbol`] - symbol tekstu jawnego powtarza się
+    /// * [`Error::KeyTooShort`] - ilość pozycji różni się od ilości symboli
+    pub fn labeled(labels: Vec<char>, data: Vec<String>) -> Result<Key, Error> {
+        if data.len() != labels.len() {
+            return Err(Error::KeyTooShort { expected: labels.len(), found: data.len() });
+        }
+        let mut seen: HashSet<char> = HashSet::new();
+        if let Some(&character) = labels.iter().find(|&&c: &&char| !seen.insert(c)) {
+            return Err(Error::DuplicateSymbol { character });
+        }
+        Ok(Key { labels, ..Key::new(data, KeyRule::Labeled) })
+    }
+
+    /// Zamienia klucz na klucz z symbolami tekstu jawnego ([`KeyRule::Labeled`]),
+    /// przypisując kolejnym pozycjom kolejne symbole alfabetu (nadmiarowe pozycje są pomijane)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `alphabet` - alfabet tekstu jawnego
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyTooShort`] - klucz musi posiadać co najmniej tyle pozycji co alfabet
+    pub fn label(self, alphabet: &Alphabet) -> Result<Key, Error> {
+        if self.data.len() < alphabet.len() {
+            return Err(Error::KeyTooShort { expected: alphabet.len(), found: self.data.len() });
+        }
+        let data: Vec<String> = self.data.into_iter().take(alphabet.len()).collect();
+        Ok(Key { header: self.header, ..Key::labeled(alphabet.symbols().to_vec(), data)? })
     }
 
-    /// Zczytuje dane z pliku (katalog nadrzędny to translation_keys) i na ich podstawie tworzy instancje klucza
+    /// Tworzy klucz ze słowa kluczowego (każda litera to osobna pozycja klucza, białe znaki są pomijane)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `keyword` - słowo kluczowe (np. `"LEMON"`)
+    pub fn from_keyword(keyword: &str) -> Key {
+        let data: Vec<String> = keyword.chars()
+                                       .filter(|c: &char| !c.is_whitespace())
+                                       .map(String::from)
+                                       .collect();
+        Key::new(data, KeyRule::Row)
+    }
+
+    /// Zczytuje dane z pliku wyszukiwanego w katalogach kluczy (patrz [`search_path`]) i na ich podstawie tworzy instancje klucza
     /// 
     /// # Arguments
     /// 
@@ -76,10 +606,9 @@ impl Key {
     /// 
     /// # Errors
     /// 
-    /// Błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
-    pub fn read_translation(path: &str, rule: KeyRule) -> Result<Key, Box<dyn Error>> {
-        let path: String = format!("translation_keys/{}", path);
-        Key::read(&path, rule)
+    /// [`Error::KeyIo`] - błędy związane z odczytem pliku (np. nieistniejąca ścieżka)
+    pub fn read_translation(path: &str, rule: impl Into<Option<KeyRule>>) -> Result<Key, Error> {
+        Key::read(&translation_path(path), rule)
     }
 
     /// Zapisuje klucz do pliku
@@ -87,19 +616,73 @@ impl Key {
     /// # Arguments
     /// 
     /// * `path` - ścieżka zapisu
-    pub fn write(&self, path: &str) {
-        let output: String = self.to_string();
-        fs::write(path, output).expect("Writing error!");
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyIo`] - błędy związane z zapisem pliku (np. brak uprawnień)
+    pub fn write(&self, path: &str) -> Result<(), Error> {
+        let output: String = self.to_file_string();
+        fs::write(path, output)
+            .map_err(|source: io::Error| Error::KeyIo { path: path.to_string(), source })
     } 
 
-    /// Zapisuje klucz do pliku (katalog nadrzędny to translation_keys)
+    /// Zapisuje klucz do pliku w pierwszym katalogu kluczy (patrz [`search_path`])
     /// 
     /// # Arguments
     /// 
     /// * `path` - ścieżka zapisu
-    pub fn write_translation(&self, path: &str) {
-        let path: String = format!("translation_keys/{}", path);
-        self.write(&path); 
+    /// 
+    /// # Errors
+    /// 
+    /// [`Error::KeyIo`] - błędy związane z zapisem pliku (np. nieistniejący katalog)
+    pub fn write_translation(&self, path: &str) -> Result<(), Error> {
+        let path: PathBuf = search_path().remove(0).join(path);
+        self.write(&path.to_string_lossy())
+    }
+
+    /// Alfabet tekstu jawnego zadeklarowany w nagłówku klucza
+    pub fn alphabet(&self) -> Option<&Alphabet> {
+        self.header.as_ref().and_then(|h: &KeyHeader| h.alphabet.as_ref())
+    }
+
+    /// Symbole puste zadeklarowane w nagłówku klucza
+    pub fn nulls(&self) -> &[char] {
+        self.header.as_ref().map_or(&[], |h: &KeyHeader| &h.nulls)
+    }
+
+    /// Sprawdza klucz i zwraca listę wszystkich powtórzonych znaków wraz z miejscami ich wystąpienia
+    /// (w odczytanym pliku, patrz [`Location`]) oraz symboli pustych pokrywających się z homofonami
+    pub fn validate(&self) -> KeyReport {
+        let mut locations: HashMap<char, Vec<Location>> = HashMap::new();
+        let mut order: Vec<char> = Vec::new();
+        for (position, vec) in self.data.iter().enumerate() {
+            for (index, sign) in vec.chars().enumerate() {
+                // klucze nieodczytane z tekstu - miejsce w reprezentacji tekstowej
+                let location: Location = match self.locations.get(position).and_then(|l: &Vec<Location>| l.get(index)) {
+                    Some(&location) => location,
+                    None => match self.rule {
+                        KeyRule::Row => Location { row: position + 1, column: index + 1 },
+                        KeyRule::Column => Location { row: index + 1, column: position + 1 },
+                        // po symbolu tekstu jawnego, dwukropku i spacji
+                        KeyRule::Labeled => Location { row: position + 1, column: index + 4 }
+                    }
+                };
+                let found: &mut Vec<Location> = locations.entry(sign).or_default();
+                found.push(location);
+                if found.len() == 2 {
+                    order.push(sign);
+                }
+            }
+        }
+        let colliding_nulls: Vec<Duplicate> = self.nulls()
+                                                  .iter()
+                                                  .filter_map(|&character: &char| locations.get(&character)
+                                                      .map(|found: &Vec<Location>| Duplicate { character, locations: found.clone() }))
+                                                  .collect();
+        let duplicates: Vec<Duplicate> = order.into_iter()
+                                              .map(|character: char| Duplicate { character, locations: locations.remove(&character).unwrap() })
+                                              .collect();
+        KeyReport { duplicates, colliding_nulls }
     }
 
     /// Tworzy klucz na podstawie paramterów
@@ -112,56 +695,180 @@ impl Key {
     /// * `char_range` - znaki brane pod uwagę przy generacji
     /// * `unique` - czy klucz ma zawierać tylko unikalne znaki
     pub fn generate(rule: KeyRule, char_count: usize, max_sub_count: usize, char_range: Vec<char>, unique: bool) -> Key {
+        Key::generate_with_rng(rule, char_count, max_sub_count, char_range, unique, &mut rand::thread_rng())
+    }
+
+    /// Tworzy klucz na podstawie paramterów korzystając z podanego generatora liczb losowych
+    /// (ten sam generator z tym samym ziarnem daje zawsze ten sam klucz)
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rule` - sposób odczytu klucza
+    /// * `char_count` - ilość różnych znaków do generacji (maksymalna długość linijki/ kolumny) 
+    /// * `max_sub_count` - ilość różnych znaków do generacji na daną pozycję
+    /// * `char_range` - znaki brane pod uwagę przy generacji
+    /// * `unique` - czy klucz ma zawierać tylko unikalne znaki
+    /// * `rng` - generator liczb losowych
+    pub fn generate_with_rng(rule: KeyRule, char_count: usize, max_sub_count: usize, char_range: Vec<char>, unique: bool, 
+                             rng: &mut impl Rng) -> Key {
         match unique {
-            true => Key::unique_generate(rule, char_count, max_sub_count, char_range),
-            false => Key::non_unique_generate(rule, char_count, max_sub_count, char_range)
+            true => Key::unique_generate(rule, char_count, max_sub_count, char_range, rng),
+            false => Key::non_unique_generate(rule, char_count, max_sub_count, char_range, rng)
         }
     }
 
-    fn unique_generate(rule: KeyRule, char_count: usize, max_sub_count: usize, mut char_range: Vec<char>) -> Key {
-        let mut rng: ThreadRng = rand::thread_rng();
-        char_range.shuffle(&mut rng);
+    /// Tworzy klucz szyfru homofonicznego, w którym ilość homofonów danej litery
+    /// jest proporcjonalna do częstości jej występowania w podanym języku
+    /// (klucz [`KeyRule::Labeled`], kolejne pozycje opisane są symbolami z [`Language::mixed_alphabet`])
+    /// 
+    /// # Arguments
+    /// 
+    /// * `language` - język tekstu jawnego
+    /// * `symbol_pool` - znaki do rozdzielenia pomiędzy pozycje klucza (białe znaki są pomijane)
+    /// 
+    /// # Errors
+    /// 
+    /// * [`Error::DuplicateSymbol`] - pula zawiera powtarzający się znak
+    /// * [`Error::InsufficientSymbols`] - pula zawiera mniej znaków niż alfabet języka
+    pub fn generate_homophonic(language: Language, symbol_pool: Vec<char>) -> Result<Key, Error> {
+        Key::generate_homophonic_with_rng(language, symbol_pool, &mut rand::thread_rng())
+    }
+
+    /// Tworzy klucz szyfru homofonicznego proporcjonalny do częstości liter
+    /// korzystając z podanego generatora liczb losowych
+    /// 
+    /// # Arguments
+    /// 
+    /// * `language` - język tekstu jawnego
+    /// * `symbol_pool` - znaki do rozdzielenia pomiędzy pozycje klucza (białe znaki są pomijane)
+    /// * `rng` - generator liczb losowych
+    /// 
+    /// # Errors
+    /// 
+    /// Patrz [`Key::generate_homophonic`]
+    pub fn generate_homophonic_with_rng(language: Language, mut symbol_pool: Vec<char>, rng: &mut impl Rng) -> Result<Key, Error> {
+        symbol_pool.retain(|c: &char| !c.is_whitespace());
+        let mut seen: HashSet<char> = HashSet::new();
+        if let Some(&character) = symbol_pool.iter().find(|&&c: &&char| !seen.insert(c)) {
+            return Err(Error::DuplicateSymbol { character });
+        }
+        let weights: Vec<f64> = language.mixed_frequencies();
+        let counts: Vec<usize> = allocate(&weights, symbol_pool.len())
+            .ok_or(Error::InsufficientSymbols { required: weights.len(), found: symbol_pool.len() })?;
+
+        symbol_pool.shuffle(rng);
+        let mut symbols: std::vec::Drain<char> = symbol_pool.drain(..);
+        let data: Vec<String> = counts.iter()
+                                      .map(|&count: &usize| symbols.by_ref().take(count).collect())
+                                      .collect();
+        Key::new(data, KeyRule::Row).label(&language.mixed_alphabet())
+    }
+
+    fn unique_generate(rule: KeyRule, char_count: usize, max_sub_count: usize, mut char_range: Vec<char>, rng: &mut impl Rng) -> Key {
+        char_range.shuffle(rng);
 
         let mut data: Vec<String> = char_range.drain(..cmp::min(char_count, char_range.len()))
                                               .map(|c: char| c.to_string())
                                               .collect();
 
         for keys in &mut data {
-            let amount: usize = rng.gen_range(0..=cmp::min(char_range.len(), max_sub_count - 1));
+            let amount: usize = rng.gen_range(0..=cmp::min(char_range.len(), max_sub_count.saturating_sub(1)));
             if char_range.len() >= amount {
                 keys.extend(char_range.drain(..amount));
             }
         }
-        Key { data, rule, unique: true }
+        Key { data, rule, unique: true, header: None, labels: Vec::new(), locations: Vec::new() }
     }
 
-    fn non_unique_generate(rule: KeyRule, char_count: usize, max_sub_count: usize, mut char_range: Vec<char>) -> Key {
-        let mut rng: ThreadRng = rand::thread_rng();
-        char_range.shuffle(&mut rng);
+    fn non_unique_generate(rule: KeyRule, char_count: usize, max_sub_count: usize, mut char_range: Vec<char>, rng: &mut impl Rng) -> Key {
+        char_range.shuffle(rng);
 
         let mut data: Vec<String> = Vec::new();
         data.resize(cmp::min(char_range.len(), char_count), String::new());
 
         for keys in &mut data {
-            let amount: usize = rng.gen_range(1..=cmp::min(char_range.len(), max_sub_count));
+            let amount: usize = rng.gen_range(1..=cmp::min(char_range.len(), max_sub_count.max(1)));
             if char_range.len() >= amount {
-                keys.extend(char_range.choose_multiple(&mut rng, amount))
+                keys.extend(char_range.choose_multiple(rng, amount))
             }
         }
-        Key { data, rule, unique: false }
+        Key { data, rule, unique: false, header: None, labels: Vec::new(), locations: Vec::new() }
+    }
+}
+
+/// Rozdziela podaną ilość elementów pomiędzy pozycje proporcjonalnie do ich wag
+/// (każda pozycja otrzymuje co najmniej jeden element, reszta dzielona jest metodą największych reszt)
+/// 
+/// Zwraca `None`, gdy elementów jest mniej niż pozycji
+/// 
+/// # Arguments
+/// 
+/// * `weights` - nieujemne wagi kolejnych pozycji
+/// * `total` - ilość elementów do rozdzielenia
+pub fn allocate(weights: &[f64], total: usize) -> Option<Vec<usize>> {
+    let remaining: usize = total.checked_sub(weights.len())?;
+    let sum: f64 = weights.iter().sum();
+    let quotas: Vec<f64> = weights.iter()
+                                  .map(|&w: &f64| match sum > 0.0 {
+                                      true => w / sum * remaining as f64,
+                                      false => remaining as f64 / weights.len() as f64
+                                  })
+                                  .collect();
+    let mut counts: Vec<usize> = quotas.iter().map(|&q: &f64| 1 + q.floor() as usize).collect();
+    let assigned: usize = counts.iter().sum::<usize>() - weights.len();
+
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a: &usize, &b: &usize| (quotas[b] - quotas[b].floor()).total_cmp(&(quotas[a] - quotas[a].floor())));
+    order.iter()
+         .take(remaining - assigned)
+         .for_each(|&i: &usize| counts[i] += 1);
+    Some(counts)
+}
+
+/// Czy żaden znak nie występuje w kluczu więcej niż raz
+fn is_unique(data: &[String]) -> bool {
+    let mut seen: HashSet<char> = HashSet::new();
+    data.iter().flat_map(|v: &String| v.chars()).all(|c: char| seen.insert(c))
+}
+
+/// Opis raportu walidacji (każdy powtórzony znak w osobnej linijce)
+impl fmt::Display for KeyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for duplicate in &self.duplicates {
+            let locations: Vec<String> = duplicate.locations
+                                                  .iter()
+                                                  .map(|l: &Location| format!("({}, {})", l.row, l.column))
+                                                  .collect();
+            writeln!(f, "{:?} repeated at {}", duplicate.character, locations.join(", "))?;
+        }
+        for null in &self.colliding_nulls {
+            let locations: Vec<String> = null.locations
+                                             .iter()
+                                             .map(|l: &Location| format!("({}, {})", l.row, l.column))
+                                             .collect();
+            writeln!(f, "{:?} declared as null but used at {}", null.character, locations.join(", "))?;
+        }
+        Ok(())
     }
 }
 
 /// Konwersja klucza na ciąg znaków
-impl ToString for Key {
-    fn to_string(&self) -> String {
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.rule {
-            KeyRule::Row => self.data.join("\n"),
+            KeyRule::Row => write!(f, "{}", self.data.join("\n")),
+            KeyRule::Labeled => {
+                let lines: Vec<String> = self.labels.iter()
+                                                    .zip(&self.data)
+                                                    .map(|(label, v): (&char, &String)| format!("{}: {}", label, v))
+                                                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            },
             KeyRule::Column => {
                 let max: usize = self.data.iter()
-                                          .max_by_key(|&v: &&String| v.len())
-                                          .unwrap()
-                                          .len();
+                                          .map(|v: &String| v.chars().count())
+                                          .max()
+                                          .unwrap_or(0);
                 let mut output: String = String::new();
                 for i in 0usize..max {
                     for v in &self.data {
@@ -174,7 +881,7 @@ impl ToString for Key {
                     output.push('\n');
                 }
                 output.pop();
-                output
+                write!(f, "{}", output)
             }
         }
     }
@@ -0,0 +1,39 @@
+use szyfry::algorithms::{AffineData, CeasarData, Cipher};
+use szyfry::{Alphabet, Error, Language};
+
+#[test]
+fn encryption_known_vector() {
+    let affine: AffineData = AffineData::new(5, 8).unwrap();
+
+    assert_eq!(affine.encrypt("AFFINE cipher"), "IHHWVC swfrcp");
+    assert_eq!(affine.decrypt("IHHWVC swfrcp"), "AFFINE cipher");
+}
+
+#[test]
+fn generalises_ceasar_and_atbash() {
+    let data: &str = "Ala ma kota, 42";
+
+    assert_eq!(AffineData::new(1, 3).unwrap().encrypt(data), CeasarData::new(3).encrypt(data));
+    assert_eq!(AffineData::atbash(Alphabet::latin_upper()).encrypt(data), "Zoz nz plgz, 42");
+    assert_eq!(AffineData::new(-1, -1).unwrap().coefficients(), (25, 25));
+}
+
+#[test]
+fn polish_alphabet_round_trip() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let affine: AffineData = AffineData::with_alphabet(7, -3, Language::Polish.alphabet()).unwrap();
+
+    let encrypted: String = affine.encrypt(data);
+
+    assert_ne!(encrypted, data);
+    assert_eq!(affine.decrypt(&encrypted), data);
+}
+
+#[test]
+fn multiplier_not_coprime() {
+    let error: Error = AffineData::new(13, 1).err().unwrap();
+    assert!(matches!(error, Error::NotCoprime { multiplier: 13, modulus: 26 }));
+
+    assert!(AffineData::with_alphabet(4, 0, Language::Polish.alphabet()).is_err());
+    assert!(AffineData::with_alphabet(3, 0, Language::Polish.alphabet()).is_ok());
+}
@@ -0,0 +1,60 @@
+use szyfry::algorithms::{Cipher, CeasarData, PolyalphabeticData};
+use szyfry::utils::{Key, KeyRule};
+use szyfry::Alphabet;
+
+#[test]
+fn presets() {
+    assert_eq!(Alphabet::latin_upper().len(), 26);
+    assert_eq!(Alphabet::latin_mixed().len(), 52);
+    assert_eq!(Alphabet::polish().len(), 32);
+    assert_eq!(Alphabet::digits().len(), 10);
+    assert_eq!(Alphabet::printable_ascii().len(), 94);
+}
+
+#[test]
+fn find_ignores_case() {
+    let alphabet: Alphabet = Alphabet::polish();
+
+    assert_eq!(alphabet.index('ż'), None);
+    assert_eq!(alphabet.find('ż'), Some(31));
+    assert_eq!(alphabet.translate('ż', |index: usize| index + 1), Some('a'));
+}
+
+#[test]
+fn read_translation() {
+    let alphabet: Alphabet = Alphabet::read_translation("hex.abc").unwrap();
+
+    assert_eq!(alphabet.len(), 16);
+    assert_eq!(alphabet.index('F'), Some(15));
+}
+
+#[test]
+fn duplicate_symbol() {
+    let error: szyfry::Error = Alphabet::new(vec!['a', 'b', 'a']).unwrap_err();
+
+    assert!(matches!(error, szyfry::Error::DuplicateSymbol { character: 'a' }));
+}
+
+#[test]
+fn ceasar_digits() {
+    let ceasar: Box<dyn Cipher> = Box::new(CeasarData::with_alphabet(3, Alphabet::digits()));
+
+    assert_eq!(ceasar.encrypt("Ala 789"), "Ala 012");
+    assert_eq!(ceasar.decrypt("Ala 012"), "Ala 789");
+}
+
+#[test]
+fn polyalphabetic_custom_alphabet() {
+    let data: &str = "CAFE 0042";
+    let code: Key = Key::read_translation("ascii_only.ckey", KeyRule::Column).unwrap();
+    let alphabet: Alphabet = Alphabet::read_translation("hex.abc").unwrap();
+
+    assert!(PolyalphabeticData::with_alphabet(code, alphabet.clone()).is_err());
+
+    let code: Key = Key::new(vec![String::from("1"), String::from("F")], KeyRule::Row);
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, alphabet).unwrap();
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted, "D90D F133");
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+}
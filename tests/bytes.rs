@@ -0,0 +1,38 @@
+use szyfry::algorithms::{ByteCeasarData, ByteCipher, BytePolyalphabeticData};
+use szyfry::stream::{decrypt_bytes_stream, encrypt_bytes_stream};
+use szyfry::utils::{Key, KeyRule};
+
+#[test]
+fn ceasar_full_range() {
+    let data: Vec<u8> = (0..=255).collect();
+    let algorithm: ByteCeasarData = ByteCeasarData::new(-1);
+
+    let encrypted: Vec<u8> = algorithm.encrypt_bytes(&data);
+
+    assert_eq!(encrypted[0], 255);
+    assert_eq!(encrypted[255], 254);
+    assert_eq!(algorithm.decrypt_bytes(&encrypted), data);
+}
+
+#[test]
+fn polyalphabetic_raw_key() {
+    let algorithm: BytePolyalphabeticData = BytePolyalphabeticData::new(vec![1, 255]).unwrap();
+
+    assert_eq!(algorithm.encrypt_bytes(&[0, 0, 255, 255]), vec![1, 255, 0, 254]);
+    assert!(BytePolyalphabeticData::new(Vec::new()).is_err());
+}
+
+#[test]
+fn polyalphabetic_key_file_stream() {
+    let data: Vec<u8> = (0..100_000u32).map(|i: u32| (i * 7 % 256) as u8).collect();
+    let code: Key = Key::read_translation("ascii_only.lkey", KeyRule::Row).unwrap();
+    let algorithm: BytePolyalphabeticData = BytePolyalphabeticData::from_key(&code).unwrap();
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    encrypt_bytes_stream(&algorithm, &data[..], &mut encrypted).unwrap();
+    let mut decrypted: Vec<u8> = Vec::new();
+    decrypt_bytes_stream(&algorithm, &encrypted[..], &mut decrypted).unwrap();
+
+    assert_eq!(encrypted, algorithm.encrypt_bytes(&data));
+    assert_eq!(decrypted, data);
+}
@@ -1,5 +1,6 @@
 use szyfry::algorithms::Cipher;
 use szyfry::algorithms::ceasar::CeasarData;
+use szyfry::Language;
 
 #[test]
 fn encryption_by1() {
@@ -32,4 +33,51 @@ fn decrytpion_by2() {
     let output: String = ceasar.decrypt(data);
 
     assert_eq!(output, "abc");
-}
\ No newline at end of file
+}
+
+#[test]
+fn encryption_polish() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let ceasar: Box<dyn Cipher> = Box::new(CeasarData::with_alphabet(1, Language::Polish.alphabet()));
+
+    let output: String = ceasar.encrypt(data);
+
+    assert_eq!(output, "Źąapmd hftłb kążo");
+    assert_eq!(ceasar.decrypt(&output), data);
+}
+
+#[test]
+fn negative_and_large_offsets() {
+    let left: Box<dyn Cipher> = Box::new(CeasarData::new(-1));
+    let large: Box<dyn Cipher> = Box::new(CeasarData::new(26 * 1_000_000_007i64 + 1));
+
+    assert_eq!(left.encrypt("Ala ma kota"), "Zkz lz jnsz");
+    assert_eq!(large.encrypt("Ala ma kota"), "Bmb nb lpub");
+}
+
+#[test]
+fn decryption_inverse() {
+    let data: &str = "Zażółć gęślą jaźń, Ala ma kota!";
+    for offset in [-300i64, -27, -26, -1, 0, 1, 25, 26, 200, 255, i64::MAX, i64::MIN] {
+        let ceasar: Box<dyn Cipher> = Box::new(CeasarData::new(offset));
+        assert_eq!(ceasar.decrypt(&ceasar.encrypt(data)), data);
+    }
+}
+
+#[test]
+fn from_letter() {
+    let ceasar: CeasarData = CeasarData::from_letter('D').unwrap();
+
+    assert_eq!(ceasar.offset(), 3);
+    assert_eq!(ceasar.encrypt("abc"), "def");
+    assert!(CeasarData::from_letter('4').is_err());
+}
+
+#[test]
+fn shift_ascii_single_characters() {
+    assert_eq!(CeasarData::shift_ascii('Z', 1), 'A');
+    assert_eq!(CeasarData::shift_ascii('a', -1), 'z');
+    assert_eq!(CeasarData::shift_ascii('m', 27), 'n');
+    assert_eq!(CeasarData::shift_ascii('ż', 3), 'ż');
+    assert_eq!(CeasarData::shift_ascii('!', 3), '!');
+}
@@ -0,0 +1,319 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_szyfry"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // program może zakończyć się przed odczytaniem wejścia (np. przy błędnym kluczu)
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn encrypt_ceasar_stdin() {
+    let output: Output = run(&["encrypt", "--cipher", "ceasar", "--offset", "1"], "Ala ma kota");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Bmb nb lpub");
+}
+
+#[test]
+fn encrypt_decrypt_homophonic_pipe() {
+    let key: [&str; 6] = ["--cipher", "homophonic", "--key", "translation_keys/test.ckey", "--rule", "column"];
+    let encrypted: Output = run(&[&["encrypt"], &key[..]].concat(), "Ala ma kota");
+    let decrypted: Output = run(&[&["decrypt"], &key[..]].concat(), &String::from_utf8(encrypted.stdout).unwrap());
+
+    assert!(decrypted.status.success());
+    assert_eq!(String::from_utf8(decrypted.stdout).unwrap(), "Ala ma kota");
+}
+
+#[test]
+fn invalid_key_fails() {
+    let output: Output = run(&["encrypt", "--cipher", "homophonic", "--key", "translation_keys/ascii_only.lkey"], "Ala ma kota");
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn unknown_option_fails() {
+    let output: Output = run(&["encrypt", "--cipher", "ceasar", "--shift", "1"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn keygen_inspect() {
+    let path: String = format!("{}/cli_keygen.lkey", env!("CARGO_TARGET_TMPDIR"));
+    let generated: Output = run(&["keygen", "--count", "52", "--subs", "1", "--output", &path], "");
+    let inspected: Output = run(&["inspect", "--key", &path], "");
+
+    assert!(generated.status.success());
+    let report: String = String::from_utf8(inspected.stdout).unwrap();
+    assert!(report.contains("positions: 52"));
+    assert!(report.contains("homophonic: valid"));
+}
+
+#[test]
+fn decrypt_unknown_symbol_fails() {
+    let key: [&str; 6] = ["--cipher", "homophonic", "--key", "translation_keys/test.lkey", "--rule", "row"];
+    let strict: Output = run(&[&["decrypt"], &key[..]].concat(), "Yw ~");
+    let lenient: Output = run(&[&["decrypt", "--unknown", "keep"], &key[..]].concat(), "Yw ~");
+
+    assert_eq!(strict.status.code(), Some(1));
+    assert_eq!(String::from_utf8(lenient.stdout).unwrap(), "AB ~");
+}
+
+#[test]
+fn ceasar_letter_offset() {
+    let output: Output = run(&["decrypt", "--cipher", "ceasar", "--offset", "B"], "Bmb nb lpub");
+
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Ala ma kota");
+}
+
+#[test]
+fn polyalphabetic_advance_letters() {
+    let output: Output = run(&["encrypt", "--cipher", "polyalphabetic", "--key", "translation_keys/ascii_only.lkey",
+                               "--lang", "en", "--advance", "letters"], "Ala ma");
+
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "Ldk jq");
+}
+
+#[test]
+fn polyalphabetic_keyword() {
+    let output: Output = run(&["encrypt", "--cipher", "polyalphabetic", "--keyword", "LEMON"], "ATTACKATDAWN");
+
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "LXFOPVEFRNHR");
+}
+
+#[test]
+fn binary_roundtrip() {
+    let input: String = format!("{}/cli_binary.bin", env!("CARGO_TARGET_TMPDIR"));
+    let encrypted: String = format!("{}/cli_binary.enc", env!("CARGO_TARGET_TMPDIR"));
+    let data: Vec<u8> = (0..=255).collect();
+    std::fs::write(&input, &data).unwrap();
+
+    let encryption: Output = run(&["encrypt", "--binary", "--cipher", "p", "--keyword", "klucz", "--input", &input, "--output", &encrypted], "");
+    let decryption: Output = run(&["decrypt", "--binary", "--cipher", "p", "--keyword", "klucz", "--input", &encrypted], "");
+
+    assert!(encryption.status.success());
+    assert_ne!(std::fs::read(&encrypted).unwrap(), data);
+    assert_eq!(decryption.stdout, data);
+}
+
+#[test]
+fn seeded_keygen_and_encryption() {
+    let keygen: [&str; 7] = ["keygen", "--count", "52", "--subs", "3", "--seed", "2022"];
+    let first: Output = run(&keygen, "");
+    let second: Output = run(&keygen, "");
+    assert_eq!(first.stdout, second.stdout);
+
+    let path: String = format!("{}/cli_seeded.lkey", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, &first.stdout).unwrap();
+    let encrypt: [&str; 7] = ["encrypt", "--cipher", "homophonic", "--key", &path, "--seed", "1"];
+    let first: Output = run(&encrypt, "Ala ma kota");
+    let second: Output = run(&encrypt, "Ala ma kota");
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn deterministic_strategy() {
+    let args: [&str; 9] = ["encrypt", "--cipher", "homophonic", "--key", "translation_keys/test.lkey", 
+                           "--strategy", "deterministic", "--seed", "5"];
+    let first: Output = run(&args, "Ala ma kota");
+    let second: Output = run(&args, "Ala ma kota");
+    assert!(first.status.success());
+    assert_eq!(first.stdout, second.stdout);
+
+    let invalid: Output = run(&["encrypt", "--cipher", "homophonic", "--key", "translation_keys/test.lkey", "--strategy", "sometimes"], "");
+    assert_eq!(invalid.status.code(), Some(1));
+}
+
+#[test]
+fn keygen_per_cipher() {
+    let path: String = format!("{}/cli_generated.lkey", env!("CARGO_TARGET_TMPDIR"));
+    let keygen: Output = run(&["keygen", "--cipher", "homophonic", "--chars", "abcdefghijklmnopqrstuvwxyz0123456789!@#$%^&*()-_=+[]{};:<>", "--output", &path], "");
+    assert!(keygen.status.success());
+    let encrypted: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path], "Ala ma kota");
+    let decrypted: Output = run(&["decrypt", "--cipher", "homophonic", "--key", &path], &String::from_utf8(encrypted.stdout).unwrap());
+    assert_eq!(decrypted.stdout, b"Ala ma kota");
+
+    let insufficient: Output = run(&["keygen", "--cipher", "homophonic", "--chars", "abc"], "");
+    assert_eq!(insufficient.status.code(), Some(1));
+    assert!(String::from_utf8(insufficient.stderr).unwrap().contains("52"));
+
+    let polyalphabetic: Output = run(&["keygen", "--cipher", "polyalphabetic", "--count", "4", "--alphabet", "latin-upper"], "");
+    let generated: String = String::from_utf8(polyalphabetic.stdout).unwrap();
+    assert!(generated.starts_with("#szyfry-key v1\nrule: row\ncipher: polyalphabetic\nalphabet: latin-upper\n"));
+    assert_eq!(generated.split_once("---\n").unwrap().1.lines().count(), 4);
+}
+
+#[test]
+fn key_search_path() {
+    let dir: String = format!("{}/search_path_keys", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("translation_keys/test.lkey", format!("{}/custom.lkey", dir)).unwrap();
+    let search: String = std::env::join_paths(["/nonexistent", dir.as_str()]).unwrap().into_string().unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_szyfry"))
+        .args(["encrypt", "--cipher", "homophonic", "--key", "custom.lkey", "--strategy", "deterministic"])
+        .env("SZYFRY_KEYS", &search)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let _ = child.stdin.take().unwrap().write_all(b"Ala ma kota");
+    let found: Output = child.wait_with_output().unwrap();
+    assert!(found.status.success());
+
+    let expected: Output = run(&["encrypt", "--cipher", "homophonic", "--key", "translation_keys/test.lkey", "--strategy", "deterministic"], "Ala ma kota");
+    assert_eq!(found.stdout, expected.stdout);
+
+    let missing: Output = run(&["encrypt", "--cipher", "homophonic", "--key", "custom.lkey"], "Ala ma kota");
+    assert_eq!(missing.status.code(), Some(1));
+}
+
+#[test]
+fn keygen_header_detects_rule() {
+    let path: String = format!("{}/cli_header.key", env!("CARGO_TARGET_TMPDIR"));
+    let keygen: Output = run(&["keygen", "--cipher", "homophonic", "--rule", "column", "--output", &path], "");
+    assert!(keygen.status.success());
+
+    let inspected: Output = run(&["inspect", "--key", &path], "");
+    let report: String = String::from_utf8(inspected.stdout).unwrap();
+    assert!(report.contains("format: v1\ncipher: homophonic\n"));
+    assert!(report.contains("rule: column\n"));
+    assert!(report.contains("homophonic: valid"));
+
+    let encrypted: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path], "Ala ma kota");
+    let decrypted: Output = run(&["decrypt", "--cipher", "homophonic", "--key", &path], &String::from_utf8(encrypted.stdout).unwrap());
+    assert_eq!(decrypted.stdout, b"Ala ma kota");
+}
+
+#[test]
+fn labeled_key_unmapped() {
+    let args: [&str; 5] = ["encrypt", "--cipher", "homophonic", "--key", "translation_keys/labeled.hkey"];
+    let kept: Output = run(&args, "Ala 7 Ó");
+    assert!(kept.status.success());
+    assert!(String::from_utf8(kept.stdout).unwrap().ends_with(" Ó"));
+
+    let rejected: Output = run(&[&args[..], &["--unmapped", "reject"]].concat(), "Ala 7 Ó");
+    assert_eq!(rejected.status.code(), Some(1));
+    assert!(String::from_utf8(rejected.stderr).unwrap().contains("'Ó' at position 6"));
+
+    let generated: Output = run(&["keygen", "--cipher", "homophonic", "--rule", "labeled", "--alphabet", "digits"], "");
+    let generated: String = String::from_utf8(generated.stdout).unwrap();
+    assert!(generated.contains("\n0: ") && generated.contains("\n9: "));
+}
+
+#[test]
+fn keygen_nulls_round_trip() {
+    let path: String = format!("{}/cli_nulls.key", env!("CARGO_TARGET_TMPDIR"));
+    let keygen: Output = run(&["keygen", "--cipher", "homophonic", "--null-count", "4", "--output", &path], "");
+    assert!(keygen.status.success());
+
+    let encrypted: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path, "--null-rate", "0.5"], "Ala ma kota");
+    assert!(encrypted.status.success());
+    let decrypted: Output = run(&["decrypt", "--cipher", "homophonic", "--key", &path], &String::from_utf8(encrypted.stdout).unwrap());
+    assert_eq!(decrypted.stdout, b"Ala ma kota");
+
+    let invalid: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path, "--null-rate", "2"], "Ala");
+    assert_eq!(invalid.status.code(), Some(1));
+}
+
+#[test]
+fn nomenclator_codebook() {
+    let key: [&str; 6] = ["--cipher", "homophonic", "--key", "translation_keys/test.lkey", "--codebook", "historical.cbook"];
+    let encrypted: Output = run(&[&["encrypt"], &key[..]].concat(), "Jan Sobieski i wojsko");
+    let encrypted: String = String::from_utf8(encrypted.stdout).unwrap();
+    assert!(encrypted.starts_with("‡1 "));
+
+    let decrypted: Output = run(&[&["decrypt"], &key[..]].concat(), &encrypted);
+    assert_eq!(decrypted.stdout, "Jan Sobieski i wojsko".as_bytes());
+
+    let missing: Output = run(&["encrypt", "--cipher", "homophonic", "--key", "translation_keys/test.lkey", "--codebook", "missing.cbook"], "");
+    assert_eq!(missing.status.code(), Some(1));
+}
+
+#[test]
+fn numeric_keygen_round_trip() {
+    let path: String = format!("{}/cli_numeric.nkey", env!("CARGO_TARGET_TMPDIR"));
+    let keygen: Output = run(&["keygen", "--cipher", "numeric", "--lang", "pl", "--width", "3", "--seed", "4", "--output", &path], "");
+    assert!(keygen.status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 32);
+
+    for format in ["spaced", "fixed"] {
+        let encrypted: Output = run(&["encrypt", "--cipher", "numeric", "--key", &path, "--format", format], "Zażółć gęślą\njaźń");
+        let decrypted: Output = run(&["decrypt", "--cipher", "numeric", "--key", &path, "--format", format],
+                                    &String::from_utf8(encrypted.stdout).unwrap());
+        assert_eq!(String::from_utf8(decrypted.stdout).unwrap(), "ZAŻÓŁĆ GĘŚLĄ\nJAŹŃ");
+    }
+
+    let invalid: Output = run(&["keygen", "--cipher", "numeric", "--width", "7"], "");
+    assert_eq!(invalid.status.code(), Some(1));
+}
+
+#[test]
+fn affine_cipher() {
+    let encrypted: Output = run(&["encrypt", "--cipher", "affine", "--multiplier", "5", "--offset", "8"], "Ala ma kota");
+    let decrypted: Output = run(&["decrypt", "--cipher", "a", "--multiplier", "5", "--offset", "8"], &String::from_utf8(encrypted.stdout).unwrap());
+    assert_eq!(decrypted.stdout, b"Ala ma kota");
+
+    let invalid: Output = run(&["encrypt", "--cipher", "affine", "--multiplier", "2"], "Ala ma kota");
+    assert_eq!(invalid.status.code(), Some(1));
+    assert!(String::from_utf8(invalid.stderr).unwrap().contains("coprime"));
+}
+
+#[test]
+fn binary_invalid_offset() {
+    let output: Output = run(&["encrypt", "--binary", "--cipher", "ceasar", "--offset", "x1"], "");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("--offset: x1"));
+}
+
+#[test]
+fn alphabet_from_search_path() {
+    let output: Output = run(&["encrypt", "--cipher", "ceasar", "--offset", "1", "--alphabet", "hex.abc"], "09af xyz");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1Ab0 xyz");
+}
+
+#[test]
+fn keygen_header_alphabet() {
+    let path: String = format!("{}/cli_polish.lkey", env!("CARGO_TARGET_TMPDIR"));
+    let chars: String = ('!'..='~').chain('À'..='ÿ').collect();
+    let keygen: Output = run(&["keygen", "--cipher", "homophonic", "--lang", "pl", "--rule", "row", "--chars", &chars, "--seed", "5", "--output", &path], "");
+    assert!(keygen.status.success());
+    assert!(std::fs::read_to_string(&path).unwrap().contains("\nalphabet: polish-mixed\n"));
+
+    // alfabet odczytywany jest z nagłówka klucza
+    let encrypted: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path], "Zażółć gęślą jaźń");
+    let decrypted: Output = run(&["decrypt", "--cipher", "homophonic", "--key", &path], &String::from_utf8(encrypted.stdout).unwrap());
+    assert_eq!(String::from_utf8(decrypted.stdout).unwrap(), "Zażółć gęślą jaźń");
+
+    let mismatch: Output = run(&["encrypt", "--cipher", "homophonic", "--key", &path, "--lang", "en"], "Ala");
+    assert_eq!(mismatch.status.code(), Some(1));
+    assert!(String::from_utf8(mismatch.stderr).unwrap().contains("does not match key alphabet: polish-mixed"));
+}
+
+#[test]
+fn seeded_cipher_keygen_reproducible() {
+    for cipher in ["homophonic", "polyalphabetic"] {
+        let args: [&str; 5] = ["keygen", "--cipher", cipher, "--seed", "5"];
+        let first: Output = run(&args, "");
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        let second: Output = run(&args, "");
+
+        assert!(first.status.success());
+        assert!(!String::from_utf8_lossy(&first.stdout).contains("created:"));
+        assert_eq!(first.stdout, second.stdout);
+    }
+}
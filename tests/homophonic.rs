@@ -1,5 +1,7 @@
-use szyfry::algorithms::{Cipher, HomophonicData};
-use szyfry::utils::{Key, KeyRule};
+use szyfry::algorithms::{Cipher, HomophoneStrategy, HomophonicData, HomophonicKeyParams, NullPlacement, Position, Unknown};
+use szyfry::utils::{Key, KeyHeader, KeyRule};
+use szyfry::{Alphabet, Error, Language};
+use rand::{SeedableRng, rngs::StdRng};
 
 #[test]
 fn encrypt_decrypt_ckey() {
@@ -23,4 +25,241 @@ fn encrypt_decrypt_lkey() {
     let decrypted: String = algorithm.decrypt(&encrypted);
 
     assert_eq!(decrypted, data);
-}
\ No newline at end of file
+}
+
+#[test]
+fn key_too_short() {
+    let code: Key = Key::read_translation("ascii_only.lkey", KeyRule::Row).unwrap();
+    let error: szyfry::Error = HomophonicData::new(code).err().unwrap();
+
+    assert!(matches!(error, szyfry::Error::KeyTooShort { expected: 52, found: 5 }));
+}
+
+#[test]
+fn try_decrypt_unknown_symbol() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+
+    let error: szyfry::Error = algorithm.try_decrypt("Yw ~").unwrap_err();
+
+    assert!(matches!(error, szyfry::Error::UnknownSymbol { character: '~', position: 3 }));
+}
+
+#[test]
+fn decrypt_unknown_symbol_lenient() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let keep: HomophonicData = HomophonicData::new(code.clone()).unwrap().unknown(Unknown::Keep);
+    let replace: HomophonicData = HomophonicData::new(code).unwrap().unknown(Unknown::Replace('?'));
+
+    assert_eq!(keep.try_decrypt("Yw ~").unwrap(), "AB ~");
+    assert_eq!(replace.try_decrypt("Yw ~").unwrap(), "AB ?");
+    assert_eq!(replace.decrypt("Yw ~"), "AB ?");
+}
+
+#[test]
+fn encrypt_decrypt_polish() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let code: Key = Key::read_translation("polish.lkey", KeyRule::Row).unwrap();
+    let algorithm: HomophonicData = HomophonicData::with_alphabet(code, Language::Polish.mixed_alphabet()).unwrap();
+
+    let encrypted: String = algorithm.encrypt(data);
+    let decrypted: String = algorithm.try_decrypt(&encrypted).unwrap();
+
+    assert!(encrypted.is_ascii());
+    assert_eq!(decrypted, data);
+}
+
+#[test]
+fn seeded_rng_reproducible() {
+    let data: &str = "Ala ma kota";
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let first: HomophonicData = HomophonicData::with_rng(code.clone(), StdRng::seed_from_u64(7)).unwrap();
+    let second: HomophonicData = HomophonicData::with_rng(code, StdRng::seed_from_u64(7)).unwrap();
+
+    assert_eq!(first.encrypt(data), second.encrypt(data));
+}
+
+#[test]
+fn round_robin_strategy() {
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let group: String = code.data[0].clone();
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap().strategy(HomophoneStrategy::RoundRobin);
+    let data: String = "A".repeat(group.chars().count() * 2);
+
+    assert_eq!(algorithm.encrypt(&data), group.repeat(2));
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt(&data)), data);
+}
+
+#[test]
+fn least_recently_used_strategy() {
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let group: String = code.data[0].clone();
+    let algorithm: HomophonicData = HomophonicData::with_rng(code, StdRng::seed_from_u64(3))
+        .unwrap()
+        .strategy(HomophoneStrategy::LeastRecentlyUsed);
+    let count: usize = group.chars().count();
+    let encrypted: String = algorithm.encrypt(&"A".repeat(count));
+
+    let mut used: Vec<char> = encrypted.chars().collect();
+    let mut expected: Vec<char> = group.chars().collect();
+    used.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(used, expected);
+}
+
+#[test]
+fn deterministic_strategy() {
+    let data: &str = "Ala ma kota a kot ma Ale";
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let strategy: HomophoneStrategy = HomophoneStrategy::Deterministic { seed: 11 };
+    let first: HomophonicData = HomophonicData::new(code.clone()).unwrap().strategy(strategy);
+    let second: HomophonicData = HomophonicData::new(code).unwrap().strategy(strategy);
+    let encrypted: String = first.encrypt(data);
+
+    assert_eq!(encrypted, first.encrypt(data));
+    assert_eq!(encrypted, second.encrypt(data));
+    assert_eq!(first.decrypt(&encrypted), data);
+
+    let mut position: Position = Position::default();
+    let (head, tail): (&str, &str) = data.split_at(10);
+    let chunked: String = first.encrypt_at(head, &mut position) + &first.encrypt_at(tail, &mut position);
+    assert_eq!(chunked, encrypted);
+}
+
+#[test]
+fn generated_frequency_key() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let chars: Vec<char> = ('!'..='~').chain('À'..='ÿ').collect();
+    let code: Key = Key::generate_homophonic(Language::Polish, chars).unwrap();
+    let algorithm: HomophonicData = HomophonicData::with_alphabet(code.clone(), Language::Polish.mixed_alphabet()).unwrap();
+
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt(data)), data);
+
+    // klucz opisuje swój alfabet, więc nie trzeba go podawać
+    assert_eq!(code.rule, KeyRule::Labeled);
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+    assert_eq!(algorithm.alphabet(), &Language::Polish.mixed_alphabet());
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt(data)), data);
+}
+
+#[test]
+fn generate_key_valid() {
+    let params: HomophonicKeyParams = HomophonicKeyParams { max_homophones: 4, ..HomophonicKeyParams::default() };
+    let code: Key = HomophonicData::generate_key_with_rng(&params, &mut StdRng::seed_from_u64(9)).unwrap();
+
+    assert_eq!(code.data.len(), 52);
+    assert!(code.data.iter().all(|v: &String| (1..=4).contains(&v.chars().count())));
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt("Ala ma kota")), "Ala ma kota");
+}
+
+#[test]
+fn generate_key_insufficient() {
+    let params: HomophonicKeyParams = HomophonicKeyParams { symbols: ('a'..='z').collect(), ..HomophonicKeyParams::default() };
+    assert!(matches!(HomophonicData::generate_key(&params), 
+                     Err(Error::InsufficientSymbols { required: 52, found: 26 })));
+}
+
+#[test]
+fn repeated_symbol_in_different_groups() {
+    let mut data: Vec<String> = ('!'..='~').take(52).map(String::from).collect();
+    data[10].push('#');
+    let code: Key = Key::new(data, KeyRule::Row);
+
+    assert!(matches!(HomophonicData::new(code), Err(Error::NonUniqueKey { character: '#', line: 11 })));
+}
+
+#[test]
+fn labeled_key_symbols() {
+    let data: &str = "Ala ma kota, 7 Żółtych kot.";
+    let code: Key = Key::read_translation("labeled.hkey", None).unwrap();
+    assert_eq!(code.rule, KeyRule::Labeled);
+    assert_eq!(code.labels()[..3], ['A', 'l', 'a']);
+    assert_eq!(code.data[7], "%#");
+
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+    let encrypted: String = algorithm.encrypt(data);
+    assert!("%#".contains(encrypted.chars().nth(13).unwrap()));
+    assert!(encrypted.ends_with('~'));
+    // litery bez homofonów przepisywane są bez zmian
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+
+    let strict: HomophonicData = algorithm.unmapped(Unknown::Reject);
+    assert!(matches!(strict.try_encrypt(data), Err(Error::UnknownSymbol { character: 'ł', position: 17 })));
+    assert_eq!(strict.try_encrypt("Ala ma kota.").map(|e: String| strict.decrypt(&e)).unwrap(), "Ala ma kota.");
+}
+
+#[test]
+fn labeled_key_with_alphabet() {
+    let code: Key = Key::parse("b: 12\na: 34", KeyRule::Labeled).unwrap();
+    let algorithm: HomophonicData = HomophonicData::with_alphabet(code.clone(), Alphabet::latin_mixed()).unwrap();
+    assert_eq!(algorithm.decrypt("1324"), "baba");
+
+    assert!(matches!(HomophonicData::with_alphabet(code, Alphabet::digits()), 
+                     Err(Error::NonAlphabeticKey { character: 'b', line: 1 })));
+}
+
+#[test]
+fn nulls_inserted_and_stripped() {
+    let data: &str = "Ala ma kota";
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let algorithm: HomophonicData = HomophonicData::new(code.clone())
+        .unwrap()
+        .null_symbols(vec!['€', '£'])
+        .unwrap()
+        .null_placement(NullPlacement::Interval { every: 2 });
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted.chars().filter(|c: &char| *c == '€' || *c == '£').count(), 4);
+    assert_eq!(encrypted.chars().nth(2), Some('€'));
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+
+    let random: HomophonicData = HomophonicData::with_rng(code, StdRng::seed_from_u64(5))
+        .unwrap()
+        .null_symbols(vec!['€'])
+        .unwrap()
+        .null_placement(NullPlacement::Random { rate: 1.0 });
+    let encrypted: String = random.encrypt(data);
+    assert_eq!(encrypted.chars().count(), data.chars().count() + 9);
+    assert_eq!(random.try_decrypt(&encrypted).unwrap(), data);
+}
+
+#[test]
+fn nulls_from_header() {
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column).unwrap();
+    let colliding: Key = code.clone().header(KeyHeader::new().nulls(vec!['€', 'A']));
+    assert!(!colliding.validate().is_valid());
+    assert_eq!(colliding.validate().colliding_nulls[0].character, 'A');
+    assert!(matches!(HomophonicData::new(colliding), Err(Error::NullCollision { character: 'A' })));
+
+    let text: String = code.header(KeyHeader::new().nulls(vec!['€', '£'])).to_file_string();
+    assert!(text.contains("\nnulls: €£\n"));
+    let algorithm: HomophonicData = HomophonicData::new(Key::parse(&text, None).unwrap()).unwrap();
+    assert_eq!(algorithm.decrypt(&(algorithm.encrypt("Ala") + "€£")), "Ala");
+}
+
+#[test]
+fn generate_key_with_nulls() {
+    let params: HomophonicKeyParams = HomophonicKeyParams { nulls: 5, ..HomophonicKeyParams::default() };
+    let code: Key = HomophonicData::generate_key(&params).unwrap();
+    assert_eq!(code.nulls().len(), 5);
+    assert!(code.validate().is_valid());
+    assert!(HomophonicData::new(code).is_ok());
+
+    let params: HomophonicKeyParams = HomophonicKeyParams { nulls: 50, ..HomophonicKeyParams::default() };
+    assert!(matches!(HomophonicData::generate_key(&params), Err(Error::InsufficientSymbols { required: 102, found: 94 })));
+}
+
+#[test]
+fn unmapped_symbol_collides_with_homophone() {
+    let data: &str = "Room 7, 1984!";
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+
+    // cyfry i interpunkcja są homofonami w kluczu z drukowalnymi znakami ASCII
+    assert!(matches!(algorithm.try_encrypt(data), Err(Error::UnknownSymbol { character: '7', position: 5 })));
+    let encrypted: String = algorithm.encrypt(data);
+    assert_eq!(encrypted.chars().filter(|&c: &char| c == char::REPLACEMENT_CHARACTER).count(), 7);
+    assert_eq!(algorithm.decrypt(&encrypted), "Room \u{FFFD}\u{FFFD} \u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}");
+    assert!(algorithm.try_decrypt(&encrypted).is_err());
+}
@@ -0,0 +1,81 @@
+use szyfry::algorithms::{Cipher, Codebook, HomophoneStrategy, HomophonicData, NomenclatorData, Unknown};
+use szyfry::utils::{Key, KeyRule};
+use szyfry::Error;
+
+fn nomenclator(strategy: HomophoneStrategy) -> NomenclatorData {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let homophonic: HomophonicData = HomophonicData::new(code).unwrap().strategy(strategy);
+    NomenclatorData::new(homophonic, Codebook::read_translation("historical.cbook").unwrap()).unwrap()
+}
+
+#[test]
+fn encrypt_decrypt_codewords() {
+    let data: &str = "Jan Sobieski i wojsko ruszyli na Warszawa i Kraków czeka";
+    let algorithm: NomenclatorData = nomenclator(HomophoneStrategy::Random);
+
+    let encrypted: String = algorithm.encrypt(data);
+    let decrypted: String = algorithm.try_decrypt(&encrypted).unwrap();
+
+    assert!(encrypted.starts_with("‡1 "));
+    assert!(!encrypted.contains("Kraków"));
+    assert_eq!(decrypted, data);
+}
+
+#[test]
+fn longest_match_on_word_boundaries() {
+    let algorithm: NomenclatorData = nomenclator(HomophoneStrategy::Deterministic { seed: 3 });
+
+    assert_eq!(algorithm.encrypt("Jan Sobieski"), "‡1");
+    assert!(algorithm.encrypt("Jan Kowalski").starts_with("‡2 "));
+    assert!(!algorithm.encrypt("Janek kingdom").contains(['‡', '¶']));
+}
+
+#[test]
+fn code_groups_follow_strategy() {
+    let algorithm: NomenclatorData = nomenclator(HomophoneStrategy::RoundRobin);
+
+    assert_eq!(algorithm.encrypt("king king king"), "¶3 ¶4 ¶3");
+    assert_eq!(algorithm.decrypt("¶4¶3"), "kingking");
+}
+
+#[test]
+fn codebook_format_errors() {
+    assert!(matches!(Codebook::parse("king: ¶1\n\nking: ¶2"), Err(Error::DuplicateCodeword { line: 3, .. })));
+    assert!(matches!(Codebook::parse("# komentarz\nking ¶1"), Err(Error::MalformedCodebookLine { line: 2 })));
+    assert!(matches!(Codebook::parse("king:"), Err(Error::MalformedCodebookLine { line: 1 })));
+    let error: Error = Codebook::parse("king: ¶1\narmy: ¶12").unwrap_err();
+    assert!(matches!(error, Error::AmbiguousCodeGroup { group } if group == "¶1"));
+
+    let codebook: Codebook = Codebook::parse("king: ¶1 ¶2\narmy: †").unwrap();
+    assert_eq!(codebook.groups("king").unwrap(), ["¶1", "¶2"]);
+    assert_eq!(Codebook::parse(&codebook.to_string()).unwrap(), codebook);
+}
+
+#[test]
+fn code_group_collides_with_homophone() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let homophonic: HomophonicData = HomophonicData::new(code).unwrap();
+    let error: Error = NomenclatorData::new(homophonic, Codebook::parse("king: Y1").unwrap()).err().unwrap();
+
+    assert!(matches!(error, Error::AmbiguousCodeGroup { group } if group == "Y1"));
+}
+
+#[test]
+fn unknown_group_rejected() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let homophonic: HomophonicData = HomophonicData::new(code).unwrap().unknown(Unknown::Reject);
+    let algorithm: NomenclatorData = NomenclatorData::new(homophonic, Codebook::parse("king: ¶1").unwrap()).unwrap();
+
+    assert!(matches!(algorithm.try_decrypt("Y ¶2"), Err(Error::UnknownSymbol { character: '¶', position: 2 })));
+}
+
+#[test]
+fn copied_sign_starting_group_rejected() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let algorithm: NomenclatorData = NomenclatorData::new(HomophonicData::new(code).unwrap(), Codebook::parse("king: §§").unwrap()).unwrap();
+
+    assert!(matches!(algorithm.try_encrypt("Ala §§ ma"), Err(Error::UnknownSymbol { character: '§', position: 4 })));
+    let encrypted: String = algorithm.encrypt("Ala §§ ma");
+    assert!(!encrypted.contains('§'));
+    assert_eq!(algorithm.decrypt(&encrypted), "Ala \u{FFFD}\u{FFFD} ma");
+}
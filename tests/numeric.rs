@@ -0,0 +1,101 @@
+use szyfry::algorithms::{Cipher, HomophoneStrategy, NumericFormat, NumericHomophonicData, NumericKey, NumericKeyParams, Unknown};
+use szyfry::{Error, Language};
+use rand::{SeedableRng, rngs::StdRng};
+
+fn generated(width: usize) -> NumericKey {
+    let params: NumericKeyParams = NumericKeyParams::for_language(Language::English, width);
+    NumericHomophonicData::generate_key_with_rng(&params, &mut StdRng::seed_from_u64(7)).unwrap()
+}
+
+#[test]
+fn generate_key_by_frequency() {
+    let key: NumericKey = generated(2);
+    let mut codes: Vec<u32> = key.codes().iter().flatten().copied().collect();
+    codes.sort_unstable();
+
+    assert_eq!(key.labels().len(), 26);
+    assert_eq!(codes, (0..100).collect::<Vec<u32>>());
+    assert!(key.codes()[4].len() > key.codes()[25].len());
+    assert!(key.codes().iter().all(|codes: &Vec<u32>| !codes.is_empty()));
+    assert_eq!(generated(3).codes().iter().map(Vec::len).sum::<usize>(), 1000);
+}
+
+#[test]
+fn encrypt_decrypt_spaced() {
+    let algorithm: NumericHomophonicData = NumericHomophonicData::new(generated(2)).unwrap();
+
+    let encrypted: String = algorithm.encrypt("Ala ma  kota\nDruga linia");
+    let decrypted: String = algorithm.try_decrypt(&encrypted).unwrap();
+
+    assert_eq!(encrypted.lines().next().unwrap().split(' ').count(), 12);
+    assert!(encrypted.split(['\n', ' ']).all(|token: &str| token.is_empty() || token.len() == 2));
+    assert_eq!(decrypted, "ALA MA  KOTA\nDRUGA LINIA");
+}
+
+#[test]
+fn encrypt_decrypt_fixed() {
+    let algorithm: NumericHomophonicData = NumericHomophonicData::new(generated(3)).unwrap()
+                                                                                   .format(NumericFormat::Fixed)
+                                                                                   .strategy(HomophoneStrategy::Deterministic { seed: 1 });
+
+    let encrypted: String = algorithm.encrypt("Ala ma kota");
+
+    assert_eq!(encrypted.len(), 9 * 3 + 2);
+    assert_eq!(encrypted, algorithm.encrypt("Ala ma kota"));
+    assert_eq!(algorithm.decrypt(&encrypted), "ALA MA KOTA");
+}
+
+#[test]
+fn key_file_round_trip() {
+    let key: NumericKey = generated(2);
+    assert_eq!(NumericKey::parse(&key.to_string()).unwrap(), key);
+
+    let parsed: NumericKey = NumericKey::parse("A: 007 031\n\nB: 100").unwrap();
+    assert_eq!((parsed.width(), parsed.codes()), (3, &[vec![7, 31], vec![100]][..]));
+}
+
+#[test]
+fn key_errors() {
+    assert!(matches!(NumericKey::parse("A: 07 31\nB: 100"), Err(Error::MalformedKeyLine { line: 2 })));
+    assert!(matches!(NumericKey::parse("A: 07 x1"), Err(Error::MalformedKeyLine { line: 1 })));
+    assert!(matches!(NumericKey::parse("A: 07\nB: 07"), Err(Error::DuplicateCode { code: 7 })));
+    assert!(matches!(NumericKey::parse("A: 00001"), Err(Error::InvalidCodeWidth { width: 5 })));
+    assert!(matches!(NumericKey::new(vec!['A'], vec![vec![100]], 2), Err(Error::CodeOutOfRange { code: 100, width: 2 })));
+
+    let params: NumericKeyParams = NumericKeyParams::for_language(Language::Polish, 1);
+    assert!(matches!(NumericHomophonicData::generate_key(&params), Err(Error::InsufficientSymbols { required: 32, found: 10 })));
+}
+
+#[test]
+fn unknown_code() {
+    let key: NumericKey = NumericKey::parse("A: 01 02\nB: 03").unwrap();
+    let algorithm: NumericHomophonicData = NumericHomophonicData::new(key).unwrap().unknown(Unknown::Reject);
+
+    assert_eq!(algorithm.try_decrypt("01 03  02").unwrap(), "AB A");
+    assert!(matches!(algorithm.try_decrypt("01 04"), Err(Error::UnknownCode { code, position: 3 }) if code == "04"));
+    assert!(matches!(algorithm.unmapped(Unknown::Reject).try_encrypt("AC"), Err(Error::UnknownSymbol { character: 'C', position: 1 })));
+}
+
+#[test]
+fn unknown_code_handled_whole() {
+    let key: NumericKey = NumericKey::parse("A: 01 02\nB: 03").unwrap();
+    let kept: NumericHomophonicData = NumericHomophonicData::new(key.clone()).unwrap().unknown(Unknown::Keep);
+    let replaced: NumericHomophonicData = NumericHomophonicData::new(key).unwrap().unknown(Unknown::Replace('?'));
+
+    assert_eq!(kept.try_decrypt("01 99 03").unwrap(), "A99B");
+    assert_eq!(replaced.try_decrypt("01 99 03").unwrap(), "A?B");
+    assert_eq!(replaced.format(NumericFormat::Fixed).try_decrypt("019903").unwrap(), "A?B");
+}
+
+#[test]
+fn plaintext_digits_rejected() {
+    let algorithm: NumericHomophonicData = NumericHomophonicData::new(generated(2)).unwrap().format(NumericFormat::Fixed);
+
+    // przepisane cyfry zostałyby odczytane jako kody
+    assert!(matches!(algorithm.try_encrypt("AGENT 007"), Err(Error::UnknownSymbol { character: '0', position: 6 })));
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt("AGENT 007")), "AGENT \u{FFFD}\u{FFFD}\u{FFFD}");
+
+    let replaced: NumericHomophonicData = NumericHomophonicData::new(generated(2)).unwrap().unmapped(Unknown::Replace('7'));
+    assert!(replaced.try_encrypt("A!").is_err());
+    assert_eq!(algorithm.try_encrypt("A, B.").map(|encrypted: String| algorithm.decrypt(&encrypted)).unwrap(), "A, B.");
+}
@@ -1,4 +1,7 @@
+use rand::{SeedableRng, rngs::StdRng};
 use szyfry::utils::*;
+use szyfry::algorithms::HomophonicData;
+use szyfry::{Alphabet, Error, Language};
 
 #[test]
 fn parse_code_1() {
@@ -63,4 +66,200 @@ r
 !
 h
 ""#);
-}
\ No newline at end of file
+}
+
+#[test]
+fn missing_file() {
+    let error: szyfry::Error = Key::read("translation_keys/missing.ckey", KeyRule::Column).unwrap_err();
+    assert!(matches!(error, szyfry::Error::KeyIo { .. }));
+}
+
+#[test]
+fn generate_with_seed() {
+    let chars: Vec<char> = ('!'..='~').collect();
+    let first: Key = Key::generate_with_rng(KeyRule::Row, 52, 3, chars.clone(), true, &mut StdRng::seed_from_u64(42));
+    let second: Key = Key::generate_with_rng(KeyRule::Row, 52, 3, chars, true, &mut StdRng::seed_from_u64(42));
+
+    assert_eq!(first.to_string(), second.to_string());
+}
+
+#[test]
+fn generate_homophonic_frequency() {
+    let chars: Vec<char> = ('!'..='~').collect();
+    let code: Key = Key::generate_homophonic_with_rng(Language::English, chars, &mut StdRng::seed_from_u64(1)).unwrap();
+    let counts: Vec<usize> = code.data.iter().map(|v: &String| v.chars().count()).collect();
+
+    assert_eq!(code.data.len(), 52);
+    assert_eq!(counts.iter().sum::<usize>(), 94);
+    assert!(counts.iter().all(|&c: &usize| c >= 1));
+    // E jest częstsze niż Z
+    assert!(counts[4] > counts[25]);
+    assert!(code.unique);
+}
+
+#[test]
+fn generate_homophonic_insufficient() {
+    let chars: Vec<char> = ('0'..='9').collect();
+    assert!(matches!(Key::generate_homophonic(Language::Polish, chars), 
+                     Err(Error::InsufficientSymbols { required: 64, found: 10 })));
+    assert!(matches!(Key::generate_homophonic(Language::English, vec!['a'; 60]), 
+                     Err(Error::DuplicateSymbol { character: 'a' })));
+}
+
+#[test]
+fn generate_homophonic_skips_whitespace() {
+    let chars: Vec<char> = (' '..='~').chain(['\t', '\n']).collect();
+    let code: Key = Key::generate_homophonic_with_rng(Language::English, chars, &mut StdRng::seed_from_u64(2)).unwrap();
+
+    assert_eq!(code.data.iter().map(|v: &String| v.chars().count()).sum::<usize>(), 94);
+    assert!(code.data.iter().all(|v: &String| !v.contains(char::is_whitespace)));
+    assert_eq!(Key::parse(&code.to_string(), KeyRule::Labeled).unwrap().data, code.data);
+}
+
+#[test]
+fn allocate_largest_remainder() {
+    assert_eq!(allocate(&[1.0, 1.0, 2.0], 7), Some(vec![2, 2, 3]));
+    assert_eq!(allocate(&[3.0, 1.0], 2), Some(vec![1, 1]));
+    assert_eq!(allocate(&[3.0, 1.0], 1), None);
+}
+
+#[test]
+fn unique_per_character() {
+    let code: Key = Key::new(vec![String::from("ab"), String::from("cd"), String::from("eb")], KeyRule::Row);
+    assert!(!code.unique);
+    assert!(Key::new(vec![String::from("ab"), String::from("cd")], KeyRule::Row).unique);
+}
+
+#[test]
+fn validate_report() {
+    let code: Key = Key::new(vec![String::from("ab"), String::from("cad"), String::from("eba")], KeyRule::Row);
+    let report: KeyReport = code.validate();
+    assert!(!report.is_valid());
+    assert_eq!(report.duplicates, vec![
+        Duplicate { character: 'a', locations: vec![Location { row: 1, column: 1 }, Location { row: 2, column: 2 }, Location { row: 3, column: 3 }] },
+        Duplicate { character: 'b', locations: vec![Location { row: 1, column: 2 }, Location { row: 3, column: 2 }] }
+    ]);
+    assert_eq!(report.to_string(), "'a' repeated at (1, 1), (2, 2), (3, 3)\n'b' repeated at (1, 2), (3, 2)\n");
+
+    let column: Key = code.rule(KeyRule::Column);
+    assert_eq!(column.validate().duplicates[1].locations, vec![Location { row: 2, column: 1 }, Location { row: 2, column: 3 }]);
+    assert!(Key::read_translation("test.ckey", KeyRule::Column).unwrap().validate().is_valid());
+}
+
+#[test]
+fn parse_matches_read() {
+    let text: String = std::fs::read_to_string("translation_keys/test.ckey").unwrap();
+    let parsed: Key = Key::parse(&text, KeyRule::Column).unwrap();
+    let read: Key = Key::read("translation_keys/test.ckey", KeyRule::Column).unwrap();
+    assert_eq!(parsed.data, read.data);
+
+    let from_reader: Key = Key::from_reader(text.as_bytes(), KeyRule::Column).unwrap();
+    assert_eq!(from_reader.data, read.data);
+}
+
+#[test]
+fn parse_empty_and_invalid() {
+    assert!(Key::parse("", KeyRule::Column).unwrap().data.is_empty());
+    assert!(Key::parse("", KeyRule::Row).unwrap().data.is_empty());
+    assert_eq!(Key::parse("a b\ncd\n", KeyRule::Row).unwrap().data, vec!["ab", "cd"]);
+    assert!(matches!(Key::from_reader(&[0xff, 0xfe][..], KeyRule::Row), Err(Error::Io(_))));
+}
+
+#[test]
+fn header_round_trip() {
+    let code: Key = Key::read_translation("test.ckey", KeyRule::Column)
+        .unwrap()
+        .header(KeyHeader::new().cipher("homophonic").alphabet(&Alphabet::latin_mixed()));
+    let text: String = code.to_file_string();
+    assert!(text.starts_with("#szyfry-key v1\nrule: column\ncipher: homophonic\nalphabet: latin-mixed\ncreated: "));
+
+    // sposób odczytu z nagłówka ma pierwszeństwo przed argumentem
+    let parsed: Key = Key::parse(&text, KeyRule::Row).unwrap();
+    assert_eq!(parsed.rule, KeyRule::Column);
+    assert_eq!(parsed.data, code.data);
+    let header: &KeyHeader = parsed.metadata().unwrap();
+    assert_eq!(header.cipher.as_deref(), Some("homophonic"));
+    assert_eq!(header.checksum, Some(code.checksum()));
+    assert_eq!(parsed.alphabet(), Some(&Alphabet::latin_mixed()));
+}
+
+#[test]
+fn header_alphabet() {
+    let custom: Alphabet = Alphabet::new("XYZ:".chars().collect()).unwrap();
+    let text: String = Key::from_keyword("ZX").header(KeyHeader::new().alphabet(&custom)).to_file_string();
+    assert!(text.contains("\nalphabet: \"XYZ:\"\n"));
+    assert_eq!(Key::parse(&text, None).unwrap().alphabet(), Some(&custom));
+
+    // symbole tworzące nazwę wbudowanego alfabetu oraz białe znaki nie zmieniają się po odczycie
+    for symbols in ["polish", "latin", " \t\"\\ab", "ab\n\r "] {
+        let custom: Alphabet = Alphabet::new(symbols.chars().collect()).unwrap();
+        let header: KeyHeader = KeyHeader::new().alphabet(&custom);
+        let parsed: Key = Key::parse(&format!("{}a", header), None).unwrap();
+        assert_eq!(parsed.metadata().unwrap().alphabet, Some(custom));
+    }
+
+    let polish: String = Key::from_keyword("Ż").header(KeyHeader::new().alphabet(&Language::Polish.alphabet())).to_file_string();
+    assert!(polish.contains("\nalphabet: polish\n"));
+    assert_eq!(Key::parse(&polish, None).unwrap().alphabet(), Some(&Alphabet::polish()));
+    assert_eq!(Key::from_keyword("A").alphabet(), None);
+    assert!(matches!(Key::parse("#szyfry-key v1\nalphabet: AA\n---\nA", None), Err(Error::InvalidKeyHeader { line: 2 })));
+    assert!(matches!(Key::parse("#szyfry-key v1\nalphabet: \"A\\x\"\n---\nA", None), Err(Error::InvalidKeyHeader { line: 2 })));
+}
+
+#[test]
+fn header_errors() {
+    let text: String = Key::from_keyword("LEMON").header(KeyHeader::new()).to_file_string();
+    assert!(matches!(Key::parse(&text.replace("LEMON", "LIMON").replace("\nE\n", "\nI\n"), None), 
+                     Err(Error::ChecksumMismatch { .. })));
+    assert!(matches!(Key::parse("#szyfry-key v2\n---\nA", None), Err(Error::UnsupportedKeyVersion { version: 2 })));
+    assert!(matches!(Key::parse("#szyfry-key v1\nrule: diagonal\n---\nA", None), Err(Error::InvalidKeyHeader { line: 2 })));
+    assert!(matches!(Key::parse("#szyfry-key v1\nrule: row\nA", None), Err(Error::InvalidKeyHeader { line: 3 })));
+}
+
+#[test]
+fn validate_file_locations() {
+    let header: String = KeyHeader::new().created(None).to_string();
+    let row: Key = Key::parse(&format!("{}ab\n\nc  a\n", header), KeyRule::Row).unwrap();
+    let rows: usize = header.lines().count();
+    assert_eq!(row.validate().duplicates[0].locations, vec![Location { row: rows + 1, column: 1 }, Location { row: rows + 3, column: 4 }]);
+
+    let labeled: Key = Key::parse(&format!("{}A: xy\n\n  B:  z x", header), KeyRule::Labeled).unwrap();
+    assert_eq!(labeled.validate().duplicates[0].locations, vec![Location { row: rows + 1, column: 4 }, Location { row: rows + 3, column: 9 }]);
+    let error: Error = HomophonicData::new(labeled).err().unwrap();
+    assert!(matches!(error, Error::NonUniqueKey { character: 'x', line } if line == rows + 3));
+
+    let column: Key = Key::parse("a b\nc a", KeyRule::Column).unwrap();
+    assert_eq!(column.validate().duplicates[0].locations, vec![Location { row: 1, column: 1 }, Location { row: 2, column: 3 }]);
+}
+
+#[test]
+fn rule_from_extension() {
+    let column: Key = Key::read("translation_keys/test.ckey", None).unwrap();
+    assert_eq!(column.rule, KeyRule::Column);
+    assert_eq!(column.data, Key::read("translation_keys/test.ckey", KeyRule::Column).unwrap().data);
+    assert_eq!(Key::read_translation("test.lkey", None).unwrap().rule, KeyRule::Row);
+}
+
+#[test]
+fn labeled_round_trip() {
+    let text: &str = "A: xQ 7\n7: %#\n:: ~";
+    let code: Key = Key::parse(text, KeyRule::Labeled).unwrap();
+    assert_eq!(code.labels(), ['A', '7', ':']);
+    assert_eq!(code.data, vec!["xQ7", "%#", "~"]);
+    assert_eq!(code.to_string(), "A: xQ7\n7: %#\n:: ~");
+
+    let labeled: String = code.clone().header(KeyHeader::new()).to_file_string();
+    assert!(labeled.contains("rule: labeled\n"));
+    assert_eq!(Key::parse(&labeled, None).unwrap().labels(), code.labels());
+    assert_eq!(Key::new(vec![String::from("ab"), String::from("cb")], KeyRule::Row).validate().duplicates.len(), 1);
+    assert_eq!(Key::parse("A: ab\nB: cb", KeyRule::Labeled).unwrap().validate().duplicates[0].locations,
+               vec![Location { row: 1, column: 5 }, Location { row: 2, column: 5 }]);
+}
+
+#[test]
+fn labeled_errors() {
+    assert!(matches!(Key::parse("A: xQ\nB xQ", KeyRule::Labeled), Err(Error::MalformedKeyLine { line: 2 })));
+    assert!(matches!(Key::parse("#szyfry-key v1\n---\nA: x\nAB", KeyRule::Labeled), Err(Error::MalformedKeyLine { line: 4 })));
+    assert!(matches!(Key::parse("A: x\nA: y", KeyRule::Labeled), Err(Error::DuplicateSymbol { character: 'A' })));
+    assert!(matches!(Key::labeled(vec!['A'], vec![]), Err(Error::KeyTooShort { expected: 1, found: 0 })));
+}
@@ -1,5 +1,7 @@
-use szyfry::algorithms::{Cipher, PolyalphabeticData};
+use szyfry::algorithms::{Cipher, KeyAdvance, PolyalphabeticData, PolyalphabeticKeyParams};
 use szyfry::utils::{Key, KeyRule};
+use szyfry::Language;
+use rand::{SeedableRng, rngs::StdRng};
 
 #[test]
 fn encrypt_decrypt_ckey() {
@@ -23,4 +25,117 @@ fn encrypt_decrypt_lkey() {
     let decrypted: String = algorithm.decrypt(&encrypted);
 
     assert_eq!(decrypted, data);
-}
\ No newline at end of file
+}
+
+#[test]
+fn non_alphabetic_key() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let error: szyfry::Error = PolyalphabeticData::new(code).err().unwrap();
+
+    assert!(matches!(error, szyfry::Error::NonAlphabeticKey { character: '}', line: 1 }));
+}
+
+#[test]
+fn encrypt_decrypt_polish() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let code: Key = Key::new(vec![String::from("Ą"), String::from("ż")], KeyRule::Row);
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, Language::Polish.alphabet()).unwrap();
+
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted, "Źżaomc ffsła iązo");
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+}
+
+#[test]
+fn classic_key_advance() {
+    let data: &str = "Attack at dawn!";
+    let code: Key = Key::from_keyword("LEMON");
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, Language::English.alphabet())
+                                                           .unwrap()
+                                                           .advance(KeyAdvance::Alphabetic);
+
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted, "Lxfopv ef rnhr!");
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+}
+
+#[test]
+fn every_character_key_advance() {
+    let code: Key = Key::from_keyword("LEMON");
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, Language::English.alphabet()).unwrap();
+
+    assert_eq!(algorithm.encrypt("Attack at dawn"), "Lxfopv mh oeib");
+}
+
+#[test]
+fn from_keyword() {
+    let algorithm: PolyalphabeticData = PolyalphabeticData::from_keyword("LEMON").unwrap();
+
+    assert_eq!(algorithm.encrypt("ATTACKATDAWN"), "LXFOPVEFRNHR");
+    assert!(PolyalphabeticData::from_keyword("LEM0N").is_err());
+    assert!(PolyalphabeticData::from_keyword("").is_err());
+}
+
+#[test]
+fn encrypt_decrypt_substitution() {
+    let data: &str = "Ala ma kota";
+    let code: Key = Key::read_translation("substitution.lkey", KeyRule::Row).unwrap();
+    let algorithm: PolyalphabeticData = PolyalphabeticData::substitution(code, Language::English.alphabet()).unwrap();
+
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted, "Myl ul tfav");
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+}
+
+#[test]
+fn substitution_not_permutation() {
+    let code: Key = Key::read_translation("ascii_only.lkey", KeyRule::Row).unwrap();
+    let error: szyfry::Error = PolyalphabeticData::substitution(code, Language::English.alphabet()).err().unwrap();
+
+    assert!(matches!(error, szyfry::Error::NotPermutation { character: 'A', line: 1 }));
+}
+
+#[test]
+fn generate_key_valid() {
+    let params: PolyalphabeticKeyParams = PolyalphabeticKeyParams { length: 5, ..PolyalphabeticKeyParams::default() };
+    let code: Key = PolyalphabeticData::generate_key_with_rng(&params, &mut StdRng::seed_from_u64(4)).unwrap();
+    assert_eq!(code.data.len(), 5);
+    let algorithm: PolyalphabeticData = PolyalphabeticData::new(code).unwrap();
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt("Ala ma kota")), "Ala ma kota");
+
+    let params: PolyalphabeticKeyParams = PolyalphabeticKeyParams { 
+        alphabet: Language::Polish.alphabet(), 
+        length: 3, 
+        substitution: true 
+    };
+    let code: Key = PolyalphabeticData::generate_key(&params).unwrap();
+    let algorithm: PolyalphabeticData = PolyalphabeticData::substitution(code, Language::Polish.alphabet()).unwrap();
+    assert_eq!(algorithm.decrypt(&algorithm.encrypt("Zażółć gęślą jaźń")), "Zażółć gęślą jaźń");
+}
+
+#[test]
+fn generate_key_empty() {
+    let params: PolyalphabeticKeyParams = PolyalphabeticKeyParams { length: 0, ..PolyalphabeticKeyParams::default() };
+    assert!(PolyalphabeticData::generate_key(&params).is_err());
+}
+
+#[test]
+fn encrypt_decrypt_polish_ckey() {
+    let data: &str = "Zażółć gęślą jaźń";
+    let code: Key = Key::read_translation("polish.ckey", KeyRule::Column).unwrap();
+
+    assert_eq!(code.data, vec!["Ż", "Ą", "B"]);
+    assert_eq!(code.to_string(), "ŻĄB");
+
+    let row: Key = Key::parse("Ż\nĄ\nB", KeyRule::Row).unwrap();
+    let algorithm: PolyalphabeticData = PolyalphabeticData::with_alphabet(code, Language::Polish.alphabet()).unwrap();
+    let expected: PolyalphabeticData = PolyalphabeticData::with_alphabet(row, Language::Polish.alphabet()).unwrap();
+
+    let encrypted: String = algorithm.encrypt(data);
+
+    assert_eq!(encrypted, expected.encrypt(data));
+    assert_eq!(algorithm.decrypt(&encrypted), data);
+}
@@ -0,0 +1,88 @@
+use std::io::Read;
+use szyfry::algorithms::{Cipher, Codebook, HomophoneStrategy, HomophonicData, NomenclatorData, NumericHomophonicData, NumericKey, NumericKeyParams, PolyalphabeticData};
+use szyfry::stream::{CHUNK_SIZE, decrypt_stream, encrypt_stream};
+use szyfry::utils::{Key, KeyRule};
+use szyfry::Language;
+use rand::{SeedableRng, rngs::StdRng};
+
+/// Źródło danych zwracające co najwyżej kilka bajtów na odczyt
+struct Trickle<'a>(&'a [u8], usize);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.1 = self.1 % 3 + 1;
+        let amount: usize = self.1.min(self.0.len()).min(buf.len());
+        buf[..amount].copy_from_slice(&self.0[..amount]);
+        self.0 = &self.0[amount..];
+        Ok(amount)
+    }
+}
+
+#[test]
+fn polyalphabetic_across_chunks() {
+    let data: String = "Zażółć gęślą jaźń, Ala ma kota. ".repeat(20);
+    let algorithm: PolyalphabeticData = PolyalphabeticData::from_keyword_with_alphabet("ŻÓŁW", Language::Polish.alphabet()).unwrap();
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    encrypt_stream(&algorithm, Trickle(data.as_bytes(), 0), &mut encrypted).unwrap();
+    let mut decrypted: Vec<u8> = Vec::new();
+    decrypt_stream(&algorithm, Trickle(&encrypted, 0), &mut decrypted).unwrap();
+
+    assert_eq!(String::from_utf8(encrypted).unwrap(), algorithm.encrypt(&data));
+    assert_eq!(String::from_utf8(decrypted).unwrap(), data);
+}
+
+#[test]
+fn unknown_symbol_position() {
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let algorithm: HomophonicData = HomophonicData::new(code).unwrap();
+
+    let mut output: Vec<u8> = Vec::new();
+    let error: szyfry::Error = decrypt_stream(&algorithm, Trickle("Yw Yw ~".as_bytes(), 0), &mut output).unwrap_err();
+
+    assert!(matches!(error, szyfry::Error::UnknownSymbol { character: '~', position: 6 }));
+}
+
+#[test]
+fn invalid_utf8() {
+    let algorithm: PolyalphabeticData = PolyalphabeticData::from_keyword("LEMON").unwrap();
+
+    let mut output: Vec<u8> = Vec::new();
+    let error: szyfry::Error = encrypt_stream(&algorithm, &b"abc\xff"[..], &mut output).unwrap_err();
+
+    assert!(matches!(error, szyfry::Error::InvalidUtf8 { position: 3 }));
+}
+
+#[test]
+fn nomenclator_across_chunks() {
+    let data: String = "Jan Sobieski prowadzi wojsko na Warszawa\n".repeat(20);
+    let code: Key = Key::read_translation("test.lkey", KeyRule::Row).unwrap();
+    let homophonic: HomophonicData = HomophonicData::new(code).unwrap().strategy(HomophoneStrategy::Deterministic { seed: 7 });
+    let algorithm: NomenclatorData = NomenclatorData::new(homophonic, Codebook::read_translation("historical.cbook").unwrap()).unwrap();
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    encrypt_stream(&algorithm, Trickle(data.as_bytes(), 0), &mut encrypted).unwrap();
+    let mut decrypted: Vec<u8> = Vec::new();
+    decrypt_stream(&algorithm, Trickle(&encrypted, 0), &mut decrypted).unwrap();
+
+    assert_eq!(String::from_utf8(encrypted).unwrap(), algorithm.encrypt(&data));
+    assert_eq!(String::from_utf8(decrypted).unwrap(), data);
+}
+
+#[test]
+fn numeric_spaced_across_chunks() {
+    // linijka dłuższa niż fragment jest dzielona przed ostatnim ciągiem białych znaków
+    let data: String = "ab ".repeat(40000) + "a\t  b \nab";
+    let params: NumericKeyParams = NumericKeyParams::for_language(Language::English, 2);
+    let key: NumericKey = NumericHomophonicData::generate_key_with_rng(&params, &mut StdRng::seed_from_u64(3)).unwrap();
+    let algorithm: NumericHomophonicData = NumericHomophonicData::new(key).unwrap().strategy(HomophoneStrategy::Deterministic { seed: 5 });
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    encrypt_stream(&algorithm, data.as_bytes(), &mut encrypted).unwrap();
+    assert!(encrypted.len() > 2 * CHUNK_SIZE);
+    let mut decrypted: Vec<u8> = Vec::new();
+    decrypt_stream(&algorithm, &encrypted[..], &mut decrypted).unwrap();
+
+    assert_eq!(String::from_utf8(encrypted).unwrap(), algorithm.encrypt(&data));
+    assert_eq!(String::from_utf8(decrypted).unwrap(), data.to_uppercase());
+}